@@ -6,6 +6,26 @@ fn main() {
     println!("cargo:rustc-link-lib=static=barretenberg");
     println!("cargo:rustc-link-lib=stdc++");
 
+    // barretenberg exports no version, so record the one the library was built from if the
+    // builder provides it, and otherwise a fingerprint that tells different libraries apart.
+    println!("cargo:rerun-if-env-changed=BARRETENBERG_VERSION");
+    println!("cargo:rerun-if-changed=lib/libbarretenberg.a");
+    let version = match std::env::var("BARRETENBERG_VERSION") {
+        Ok(version) if !version.trim().is_empty() => version.trim().to_owned(),
+        _ => match std::fs::read("lib/libbarretenberg.a") {
+            Ok(lib) => format!("unknown+{:016x}", fnv1a(&lib)),
+            Err(_) => "unknown".to_owned(),
+        },
+    };
+    println!("cargo:rustc-env=BARRETENBERG_VERSION={version}");
+
+    // The `embedded-srs` feature bakes the start of a local ignition transcript into the binary.
+    println!("cargo:rerun-if-env-changed=BARRETENBERG_EMBEDDED_SRS");
+    println!("cargo:rerun-if-env-changed=BARRETENBERG_EMBEDDED_SRS_POINTS");
+    if std::env::var_os("CARGO_FEATURE_EMBEDDED_SRS").is_some() {
+        embed_srs();
+    }
+
     // The bindgen::Builder is the main entry point
     // to bindgen, and lets you build up options for
     // the resulting bindings.
@@ -13,12 +33,20 @@ fn main() {
         // Clang args so that we can compile C++ with C++20
         .clang_args(&["-std=gnu++20", "-xc++"])
         .clang_args(&["-I./"])
+        // The ECDSA bindings include their curve relative to the barretenberg directory.
+        .clang_args(&["-I./barretenberg"])
         .header_contents(
             "wrapper.hpp",
             r#"
             #include <barretenberg/dsl/acir_proofs/acir_proofs.hpp>
             #include <barretenberg/srs/c_bind.hpp>
             #include <barretenberg/examples/c_bind.hpp>
+            #include <barretenberg/crypto/pedersen_hash/c_bind.hpp>
+            #include <barretenberg/crypto/pedersen_commitment/c_bind_new.hpp>
+            #include <barretenberg/crypto/blake2s/c_bind.hpp>
+            #include <barretenberg/crypto/keccak/keccak.hpp>
+            #include <barretenberg/crypto/schnorr/c_bind.hpp>
+            #include <barretenberg/crypto/ecdsa/c_bind.h>
             "#,
         )
         .allowlist_function("acir_get_circuit_sizes")
@@ -36,6 +64,15 @@ fn main() {
         .allowlist_function("acir_serialize_verification_key_into_fields")
         .allowlist_function("srs_init_srs")
         .allowlist_function("examples_simple_create_and_verify_proof")
+        .allowlist_function("pedersen_hash_multiple_with_hash_index")
+        .allowlist_function("pedersen___plookup_commit")
+        .allowlist_function("blake2s")
+        .allowlist_function("ethash_keccak256")
+        .allowlist_function("schnorr_compute_public_key")
+        .allowlist_function("schnorr_construct_signature")
+        .allowlist_function("schnorr_verify_signature")
+        .allowlist_function("ecdsa__verify_signature")
+        .allowlist_function("ecdsa__recover_public_key_from_signature")
         .generate()
         .expect("Couldn't generate bindings!");
 
@@ -45,3 +82,72 @@ fn main() {
         .write_to_file(out_path.join("bindings.rs"))
         .expect("Couldn't write bindings!");
 }
+
+/// Size of the manifest at the start of an ignition transcript: seven big-endian `u32`s.
+const SRS_MANIFEST_SIZE: usize = 28;
+/// The number of G1 points embedded unless `BARRETENBERG_EMBEDDED_SRS_POINTS` says otherwise.
+const DEFAULT_EMBEDDED_SRS_POINTS: u32 = (1 << 12) + 1;
+
+/// Copies the first G1 points and the G2 point of the transcript `BARRETENBERG_EMBEDDED_SRS` names
+/// into `embedded_srs.bin` in `OUT_DIR`, for `srs::embedded` to include.
+///
+/// Without a transcript the file is left empty, so the crate still builds with every feature
+/// enabled, and `srs_init_embedded` reports the missing SRS when it is called.
+fn embed_srs() {
+    let srs = match std::env::var("BARRETENBERG_EMBEDDED_SRS") {
+        Ok(path) => {
+            println!("cargo:rerun-if-changed={path}");
+            read_embedded_srs(&path).unwrap_or_else(|err| {
+                eprintln!("error: cannot embed the SRS from {path}: {err}");
+                std::process::exit(1);
+            })
+        }
+        Err(_) => {
+            println!(
+                "cargo:warning=no SRS is embedded, as BARRETENBERG_EMBEDDED_SRS is not set to a \
+                 local ignition transcript such as monomial/transcript00.dat"
+            );
+            Vec::new()
+        }
+    };
+    let out_path = std::path::PathBuf::from(std::env::var("OUT_DIR").unwrap());
+    std::fs::write(out_path.join("embedded_srs.bin"), srs).unwrap();
+}
+
+fn read_embedded_srs(path: &str) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    use std::io::{Read, Seek, SeekFrom};
+
+    let num_points = match std::env::var("BARRETENBERG_EMBEDDED_SRS_POINTS") {
+        Ok(points) => points.trim().parse().map_err(|_| {
+            format!("BARRETENBERG_EMBEDDED_SRS_POINTS is not a number of points: {points}")
+        })?,
+        Err(_) => DEFAULT_EMBEDDED_SRS_POINTS,
+    };
+
+    let mut file = std::fs::File::open(path)?;
+    let mut manifest = [0u8; SRS_MANIFEST_SIZE];
+    file.read_exact(&mut manifest)?;
+    // `num_g1_points` is the fifth field of the manifest.
+    let num_g1_points = u32::from_be_bytes(manifest[16..20].try_into().unwrap());
+    if num_points > num_g1_points {
+        return Err(format!(
+            "the transcript holds {num_g1_points} G1 points, {num_points} requested"
+        )
+        .into());
+    }
+    let mut srs = vec![0u8; num_points as usize * 64];
+    file.read_exact(&mut srs)?;
+    let g2_offset = SRS_MANIFEST_SIZE as u64 + u64::from(num_g1_points) * 64;
+    file.seek(SeekFrom::Start(g2_offset))?;
+    let mut g2_point = [0u8; 128];
+    file.read_exact(&mut g2_point)?;
+    srs.extend_from_slice(&g2_point);
+    Ok(srs)
+}
+
+/// 64-bit FNV-1a, which is stable across Rust versions, unlike `DefaultHasher`.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
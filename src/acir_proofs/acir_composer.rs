@@ -1,273 +1,1239 @@
-use std::ffi::{c_char, c_void, CStr};
+use std::cell::{Cell, RefCell};
+use std::ffi::{c_char, c_void};
+use std::fmt;
+#[cfg(feature = "mmap")]
+use std::fs::File;
+use std::io::{self, Read, Write};
+use std::mem;
+#[cfg(feature = "mmap")]
+use std::path::Path;
 use std::ptr;
+use std::time::Duration;
 
-use crate::buffer::{Buffer, parse_c_str, serialize_slice};
+use super::circuit::PreparedCircuit;
+use super::proof::{proof_public_input_count, Proof, ProofArtifacts, ProofMode, ProofStats};
+use super::solidity::{self, SolidityOptions};
+use super::verification_key::{VerificationKey, VerificationKeyFields};
+use crate::buffer::{read_vector, BufferError, FieldVec, InputBuffer, OwnedBuffer};
+use crate::error::{check_error, ffi_call, BackendError, Operation};
+use crate::validation;
 use crate::{
     acir_create_proof, acir_delete_acir_composer, acir_get_circuit_sizes,
     acir_get_solidity_verifier, acir_get_verification_key, acir_init_proving_key,
     acir_init_verification_key, acir_load_verification_key, acir_new_acir_composer,
     acir_serialize_proof_into_fields, acir_serialize_verification_key_into_fields,
-    acir_verify_proof, examples_simple_create_and_verify_proof
+    acir_verify_proof,
 };
 
+/// Upper bounds on the output buffers the backend returns, checked before their contents are read
+/// so a corrupted length prefix cannot send a read far past the allocation. Each is orders of
+/// magnitude above what the call produces: an UltraPlonk proof is about 2 KiB plus 32 bytes per
+/// public input, and a verification key about 2 KiB.
+const MAX_PROOF_LEN: usize = 16 << 20;
+const MAX_VERIFICATION_KEY_LEN: usize = 1 << 20;
+const MAX_SOLIDITY_VERIFIER_LEN: usize = 4 << 20;
+/// A proof exported as fields splits every point coordinate into two field elements.
+const MAX_PROOF_FIELDS_LEN: usize = 4 * MAX_PROOF_LEN;
+
+/// The default upper bound on the size hint accepted by [`AcirComposer::new`], in gates.
+pub const DEFAULT_MAX_SIZE_HINT: u32 = 1 << 27;
+
 /// A safe wrapper around the ACIR composer from the C library.
+///
+/// The composer is `Send` but not `Sync`: it may be moved to another thread, but it must never be
+/// used from two threads at once. Composers on different threads are safe to use at the same time,
+/// but their calls into the backend take turns with each other and with every other binding in
+/// this crate, such as the hash and signature functions, as they all share barretenberg's global
+/// state, such as the generator tables it fills in on first use. This includes the CRS, so
+/// [`crate::srs::srs_init`] waits for any proving or verification in progress, although proofs
+/// started afterwards use the new CRS; [`crate::srs::srs_init_once`] only initializes it once.
 pub struct AcirComposer {
+    /// The C++ composer, or null once it has been deleted.
     ptr: *mut c_void,
+    size_hint: u32,
+    last_error: RefCell<Option<String>>,
+    /// Whether a call that computes the proving key has succeeded.
+    pk_initialized: Cell<bool>,
+    /// Whether a call that computes or loads the verification key has succeeded.
+    vk_initialized: Cell<bool>,
+    /// Whether the backend may already hold a circuit, which `acir_create_proof` would append to.
+    circuit_built: Cell<bool>,
+    /// Sizes of the circuit the backend last built, by [`AcirComposer::init_proving_key`] or
+    /// while proving.
+    circuit_sizes: Cell<Option<CircuitSizes>>,
+    /// The circuit [`AcirComposer::prove_witness`] proves witnesses of.
+    circuit: Option<PreparedCircuit>,
+    /// The key last returned by [`AcirComposer::verification_key`], until one is loaded or
+    /// computed again.
+    cached_verification_key: RefCell<Option<VerificationKey>>,
 }
 
+// SAFETY: The underlying C++ `AcirComposer` owns all of its state and holds no thread-local data, so
+// handing the sole owner to another thread is sound. Every method, including the `&self` ones,
+// mutates that state behind the FFI boundary (`init_proving_key` rebuilds the circuit,
+// `create_proof` replaces the inner composer, `verify_proof` resizes the builder's public inputs,
+// and the verification key methods overwrite the cached key), so concurrent access is not.
+// `AcirComposer` therefore deliberately stays `!Sync`.
+unsafe impl Send for AcirComposer {}
+
 impl AcirComposer {
-    /// Creates a new ACIR composer.
-    pub fn new(size_hint: u32) -> Result<Self, &'static str> {
+    /// Creates a new ACIR composer, accepting size hints up to [`DEFAULT_MAX_SIZE_HINT`] gates.
+    ///
+    /// See [`AcirComposer::with_max_size_hint`].
+    pub fn new(size_hint: u32) -> Result<Self, BackendError> {
+        Self::with_max_size_hint(size_hint, DEFAULT_MAX_SIZE_HINT)
+    }
+
+    /// Creates a new ACIR composer, accepting size hints up to `max_size_hint` gates.
+    ///
+    /// A non-zero `size_hint` is rounded up to the next power of two, the size of the evaluation
+    /// subgroup the circuit is padded to, and rejected with [`BackendError::InvalidSizeHint`] if
+    /// that exceeds `max_size_hint`. The `subgroup` size reported by [`get_circuit_sizes`] is
+    /// already a power of two and is passed through unchanged, so it can be used directly.
+    ///
+    /// The backend only records the hint here; a hint it cannot honour is reported by the first
+    /// call that builds the circuit, such as [`AcirComposer::init_proving_key`].
+    pub fn with_max_size_hint(size_hint: u32, max_size_hint: u32) -> Result<Self, BackendError> {
+        let size_hint = match size_hint.checked_next_power_of_two() {
+            Some(rounded) if size_hint != 0 && rounded <= max_size_hint => rounded,
+            _ => {
+                return Err(BackendError::InvalidSizeHint {
+                    op: Operation::new("acir_new_acir_composer").size_hint(size_hint),
+                    got: size_hint,
+                    max: max_size_hint,
+                })
+            }
+        };
+        let op = Operation::new("acir_new_acir_composer").size_hint(size_hint);
         let mut out_ptr = ptr::null_mut();
-        let error_msg_ptr = unsafe { acir_new_acir_composer(&size_hint, &mut out_ptr) };
-        if !error_msg_ptr.is_null() {
-            println!(
-                "C++ error: {}",
-                parse_c_str(error_msg_ptr).unwrap_or("Parsing c_str failed".to_string())
-            );
+        if let Err(err) = ffi_call!(op, acir_new_acir_composer(&size_hint, &mut out_ptr)) {
+            if !out_ptr.is_null() {
+                // Don't leak a composer the backend constructed before reporting the failure.
+                let delete = Operation::new("acir_delete_acir_composer");
+                let _ = ffi_call!(delete, acir_delete_acir_composer(&out_ptr));
+            }
+            return Err(err);
         }
         if out_ptr.is_null() {
-            Err("Failed to create a new ACIR composer.")
+            Err(BackendError::FfiNull { op })
         } else {
-            Ok(AcirComposer { ptr: out_ptr })
+            Ok(AcirComposer {
+                ptr: out_ptr,
+                size_hint,
+                last_error: RefCell::new(None),
+                pk_initialized: Cell::new(false),
+                vk_initialized: Cell::new(false),
+                circuit_built: Cell::new(false),
+                circuit_sizes: Cell::new(None),
+                circuit: None,
+                cached_verification_key: RefCell::new(None),
+            })
         }
     }
 
+    /// Creates a composer sized for `circuit` by [`get_circuit_sizes`] and bound to it as by
+    /// [`AcirComposer::set_constraint_system`], so [`AcirComposer::prove_witness`] needs only the
+    /// witness.
+    ///
+    /// A composer still proves a single witness, but cloning `circuit` into a composer per witness
+    /// keeps every proof on the same constraint system without passing it again.
+    pub fn with_constraint_system(circuit: PreparedCircuit) -> Result<Self, BackendError> {
+        let sizes = get_circuit_sizes(circuit.constraint_system())?;
+        let mut acir_composer = Self::new(sizes.subgroup)?;
+        acir_composer.set_constraint_system(circuit);
+        Ok(acir_composer)
+    }
+
+    /// Creates a composer that verifies proofs against `verification_key`, which is parsed once
+    /// here rather than on every [`AcirComposer::verify_proof`].
+    pub fn with_verification_key(verification_key: &VerificationKey) -> Result<Self, BackendError> {
+        Self::verifier(verification_key.as_ref())
+    }
+
+    fn verifier(verification_key: &[u8]) -> Result<Self, BackendError> {
+        // The key carries its own circuit size, so the hint is never used.
+        let acir_composer = Self::new(1)?;
+        acir_composer.load_verification_key(verification_key)?;
+        Ok(acir_composer)
+    }
+
+    /// The size hint the composer was created with, after rounding.
+    pub fn size_hint(&self) -> u32 {
+        self.size_hint
+    }
+
+    /// Whether a verification key has been computed or loaded.
+    pub(crate) fn has_verification_key(&self) -> bool {
+        self.vk_initialized.get()
+    }
+
     /// Initializes the proving key for this composer.
-    pub fn init_proving_key(&self, constraint_system_buf: &[u8]) {
-        let error_msg_ptr = unsafe {
-            acir_init_proving_key(
-                &self.ptr,
-                serialize_slice(constraint_system_buf).as_slice().as_ptr(),
-            )
-        };
-        if !error_msg_ptr.is_null() {
-            println!(
-                "C++ error: {}",
-                parse_c_str(error_msg_ptr).unwrap_or("Parsing c_str failed".to_string())
-            );
-        }
+    pub fn init_proving_key(&self, constraint_system_buf: &[u8]) -> Result<(), BackendError> {
+        let op = self
+            .operation("acir_init_proving_key")
+            .input("cs", constraint_system_buf.len());
+        check_input(&op, validation::constraint_system(constraint_system_buf))?;
+        let serialized = InputBuffer::new(constraint_system_buf).serialize();
+        self.record_circuit_sizes(&op, &serialized)?;
+        self.circuit_built.set(true);
+        ffi_call!(self; op, acir_init_proving_key(&self.ptr, serialized.as_ptr()))?;
+        self.pk_initialized.set(true);
+        Ok(())
     }
 
     /// Creates a proof using the provided constraint system buffer and witness.
+    ///
+    /// The proving key is computed as part of the call. Each composer can create a single proof:
+    /// the backend appends the witnessed circuit to any circuit it already built, here or in
+    /// [`AcirComposer::init_proving_key`], and aborts the process on the mismatch. Such calls are
+    /// therefore refused with [`BackendError::CircuitAlreadyBuilt`].
+    ///
+    /// The proof records `mode`, which [`AcirComposer::verify_proof`] defaults to.
     pub fn create_proof(
+        &self,
+        constraint_system_buf: &[u8],
+        witness: &[u8],
+        mode: ProofMode,
+    ) -> Result<Proof, BackendError> {
+        self.create_proof_buffer(constraint_system_buf, witness, mode)
+            .map(|(proof, _)| Proof::new(proof.to_vec(), mode))
+    }
+
+    /// [`AcirComposer::create_proof`] with the mode given as barretenberg's `is_recursive` flag.
+    #[deprecated(note = "pass a `ProofMode` to `AcirComposer::create_proof`")]
+    pub fn create_proof_legacy(
         &self,
         constraint_system_buf: &[u8],
         witness: &[u8],
         is_recursive: bool,
-    ) -> Result<Vec<u8>, &'static str> {
-        let mut out_ptr: *mut u8 = ptr::null_mut();
-        let error_msg_ptr = unsafe {
-            acir_create_proof(
-                &self.ptr,
-                serialize_slice(constraint_system_buf).as_slice().as_ptr(),
-                serialize_slice(witness).as_slice().as_ptr(),
-                &is_recursive,
-                &mut out_ptr,
-            )
+    ) -> Result<Proof, BackendError> {
+        let mode = ProofMode::from_is_recursive(is_recursive);
+        self.create_proof(constraint_system_buf, witness, mode)
+    }
+
+    /// Like [`AcirComposer::create_proof`], but writes the proof straight from the backend's
+    /// buffer into `writer`, e.g. a file or socket, and returns the number of bytes written.
+    pub fn create_proof_into<W: Write>(
+        &self,
+        constraint_system_buf: &[u8],
+        witness: &[u8],
+        mode: ProofMode,
+        mut writer: W,
+    ) -> Result<u64, ProofWriteError> {
+        let (mut proof, _) = self.create_proof_buffer(constraint_system_buf, witness, mode)?;
+        Ok(io::copy(&mut proof, &mut writer)?)
+    }
+
+    /// Like [`AcirComposer::create_proof`], but memory-maps the witness from the file at
+    /// `witness_path` instead of taking it in memory.
+    ///
+    /// The backend still needs the witness behind a length prefix, so it is copied once, but not
+    /// first read into a buffer of its own. The file must not be modified until the call returns.
+    #[cfg(feature = "mmap")]
+    pub fn create_proof_from_witness_file(
+        &self,
+        constraint_system_buf: &[u8],
+        witness_path: &Path,
+        mode: ProofMode,
+    ) -> Result<Proof, WitnessReadError> {
+        let file = File::open(witness_path)?;
+        // Safety: the mapping is only read, and callers must not modify the file meanwhile.
+        let witness = unsafe { memmap2::Mmap::map(&file)? };
+        Ok(self.create_proof(constraint_system_buf, &witness, mode)?)
+    }
+
+    /// Like [`AcirComposer::create_proof`], but returns the public inputs separately from the rest
+    /// of the proof, as [`Proof::split_public_inputs`] does.
+    ///
+    /// The circuit fixes how many public inputs the proof starts with, so the count is derived
+    /// from the proof's length with [`Proof::num_public_inputs`], as the verifier does.
+    pub fn create_proof_split(
+        &self,
+        constraint_system_buf: &[u8],
+        witness: &[u8],
+        mode: ProofMode,
+    ) -> Result<(Vec<[u8; 32]>, Vec<u8>), BackendError> {
+        let proof = self.create_proof(constraint_system_buf, witness, mode)?;
+        let num_public_inputs = proof.num_public_inputs().map_err(|err| {
+            BackendError::MalformedOutput {
+                op: self.operation("acir_create_proof"),
+                reason: err.to_string(),
+            }
+        })?;
+        Ok(proof.split_public_inputs(num_public_inputs))
+    }
+
+    /// Sets the circuit [`AcirComposer::prove_witness`] proves witnesses of.
+    pub fn set_constraint_system(&mut self, circuit: PreparedCircuit) {
+        self.circuit = Some(circuit);
+    }
+
+    /// Like [`AcirComposer::create_proof`], but for the circuit set with
+    /// [`AcirComposer::set_constraint_system`], which is not validated or serialized again.
+    ///
+    /// Fails with [`BackendError::ConstraintSystemNotSet`] if no circuit was set.
+    pub fn prove_witness(&self, witness: &[u8], mode: ProofMode) -> Result<Proof, BackendError> {
+        let op = self.operation("acir_create_proof");
+        let Some(circuit) = &self.circuit else {
+            return Err(BackendError::ConstraintSystemNotSet { op });
         };
-        if !error_msg_ptr.is_null() {
-            println!(
-                "C++ error: {}",
-                parse_c_str(error_msg_ptr).unwrap_or("Parsing c_str failed".to_string())
-            );
-        }
-        if out_ptr.is_null() {
-            Err("Failed to create proof.")
-        } else {
-            let result = unsafe { Buffer::from_ptr(Buffer::from_ptr(out_ptr)?.to_vec().as_slice().as_ptr())?.to_vec() };
-            Ok(result)
+        let op = op
+            .input("cs", circuit.constraint_system().len())
+            .input("witness", witness.len());
+        self.prove_serialized(op, circuit.serialized(), witness, mode)
+            .map(|(proof, _)| Proof::new(proof.to_vec(), mode))
+    }
+
+    /// Proves each of `witnesses` for the circuit set with
+    /// [`AcirComposer::set_constraint_system`], stopping at the first failure.
+    ///
+    /// A composer only proves once, so each witness is proven by a fresh composer with the same
+    /// size hint, sharing the circuit that was validated and serialized once; this composer is
+    /// left untouched. Every proof still computes its own proving key, as the backend offers no way
+    /// to hand one to another composer.
+    ///
+    /// Fails with [`BackendError::ConstraintSystemNotSet`] if no circuit was set.
+    pub fn prove_many<'a>(
+        &self,
+        witnesses: impl IntoIterator<Item = &'a [u8]>,
+        mode: ProofMode,
+    ) -> Result<Vec<Proof>, BackendError> {
+        let Some(circuit) = &self.circuit else {
+            return Err(BackendError::ConstraintSystemNotSet {
+                op: self.operation("acir_create_proof"),
+            });
+        };
+        witnesses
+            .into_iter()
+            .map(|witness| {
+                // The hint was rounded and accepted already, so it is its own bound.
+                let mut acir_composer = Self::with_max_size_hint(self.size_hint, self.size_hint)?;
+                acir_composer.set_constraint_system(circuit.clone());
+                acir_composer.prove_witness(witness, mode)
+            })
+            .collect()
+    }
+
+    fn create_proof_buffer(
+        &self,
+        constraint_system_buf: &[u8],
+        witness: &[u8],
+        mode: ProofMode,
+    ) -> Result<(OwnedBuffer, Duration), BackendError> {
+        let op = self
+            .operation("acir_create_proof")
+            .input("cs", constraint_system_buf.len())
+            .input("witness", witness.len());
+        check_input(&op, validation::constraint_system(constraint_system_buf))?;
+        let serialized = InputBuffer::new(constraint_system_buf).serialize();
+        self.prove_serialized(op, &serialized, witness, mode)
+    }
+
+    /// Proves `witness` of a constraint system already validated and serialized with its length
+    /// prefix, returning the proof and how long the backend call took.
+    fn prove_serialized(
+        &self,
+        op: Operation,
+        serialized_constraint_system: &[u8],
+        witness: &[u8],
+        mode: ProofMode,
+    ) -> Result<(OwnedBuffer, Duration), BackendError> {
+        check_input(&op, validation::witness(witness))?;
+        if self.circuit_built.get() {
+            return Err(BackendError::CircuitAlreadyBuilt { op });
         }
+        self.record_circuit_sizes(&op, serialized_constraint_system)?;
+        self.circuit_built.set(true);
+        let mut out_ptr: *mut u8 = ptr::null_mut();
+        let (result, duration) = ffi_call!(self; timed op, acir_create_proof(
+            &self.ptr,
+            serialized_constraint_system.as_ptr(),
+            InputBuffer::new(witness).serialize().as_ptr(),
+            &mode.is_recursive(),
+            &mut out_ptr,
+        ));
+        result?;
+        self.pk_initialized.set(true);
+        // The proof is returned as a length-prefixed vector wrapped in another length prefix.
+        let buffer = unsafe { take_buffer(&op, out_ptr, MAX_PROOF_LEN)? };
+        let proof = buffer
+            .into_vector()
+            .map_err(|reason| BackendError::MalformedOutput {
+                op,
+                reason: reason.to_string(),
+            })?;
+        Ok((proof, duration))
     }
 
-    pub fn load_verification_key(&self, verification_key: &[u8]) {
-        let error_msg_ptr =
-            unsafe { acir_load_verification_key(&self.ptr, verification_key.as_ptr()) };
-        if !error_msg_ptr.is_null() {
-            println!(
-                "C++ error: {}",
-                parse_c_str(error_msg_ptr).unwrap_or("Parsing c_str failed".to_string())
-            );
+    /// Like [`AcirComposer::create_proof`], but runs the proving call on tokio's blocking thread
+    /// pool so it does not stall the async runtime.
+    ///
+    /// The composer is `Send` but not `Sync`, so it cannot be borrowed by the blocking task;
+    /// instead it is moved there and handed back together with the result. If the returned future
+    /// is dropped before it completes, proving still runs to completion and the composer is freed
+    /// on the blocking thread.
+    #[cfg(feature = "tokio")]
+    pub async fn create_proof_async(
+        self,
+        constraint_system_buf: Vec<u8>,
+        witness: Vec<u8>,
+        mode: ProofMode,
+    ) -> (Self, Result<Proof, BackendError>) {
+        let task = tokio::task::spawn_blocking(move || {
+            let proof = self.create_proof(&constraint_system_buf, &witness, mode);
+            (self, proof)
+        });
+        match task.await {
+            Ok(ret) => ret,
+            Err(err) => std::panic::resume_unwind(err.into_panic()),
         }
     }
 
-    pub fn init_verification_key(&self) {
-        let error_msg_ptr = unsafe { acir_init_verification_key(&self.ptr) };
-        if !error_msg_ptr.is_null() {
-            println!(
-                "C++ error: {}",
-                parse_c_str(error_msg_ptr).unwrap_or("Parsing c_str failed".to_string())
-            );
+    /// Like [`AcirComposer::create_proof`], but also reports how long proving took and how large
+    /// the circuit was. Only the proving call itself is timed.
+    pub fn create_proof_with_stats(
+        &self,
+        constraint_system_buf: &[u8],
+        witness: &[u8],
+        mode: ProofMode,
+    ) -> Result<(Proof, ProofStats), BackendError> {
+        let (proof, duration) = self.create_proof_buffer(constraint_system_buf, witness, mode)?;
+        let proof = Proof::new(proof.to_vec(), mode);
+        let num_public_inputs = proof.num_public_inputs().map_err(|err| {
+            BackendError::MalformedOutput {
+                op: self.operation("acir_create_proof"),
+                reason: err.to_string(),
+            }
+        })?;
+        let stats = ProofStats {
+            duration,
+            proof_len: proof.as_ref().len(),
+            circuit_sizes: self
+                .circuit_sizes
+                .get()
+                .expect("circuit_sizes is recorded before proving"),
+            num_public_inputs,
+        };
+        Ok((proof, stats))
+    }
+
+    /// Loads a verification key serialized by [`AcirComposer::get_verification_key`], after
+    /// which the composer can verify proofs without a proving key.
+    pub fn load_verification_key(&self, verification_key: &[u8]) -> Result<(), BackendError> {
+        let op = self
+            .operation("acir_load_verification_key")
+            .input("vk", verification_key.len());
+        check_input(&op, validation::verification_key(verification_key))?;
+        self.cached_verification_key.take();
+        ffi_call!(self; op, acir_load_verification_key(&self.ptr, verification_key.as_ptr()))?;
+        self.vk_initialized.set(true);
+        Ok(())
+    }
+
+    /// Like [`AcirComposer::load_verification_key`], but reads the key from `reader`, e.g. an
+    /// opened key file, until it is exhausted.
+    pub fn load_verification_key_from_reader<R: Read>(
+        &self,
+        mut reader: R,
+    ) -> Result<(), KeyReadError> {
+        let mut verification_key = Vec::new();
+        reader.read_to_end(&mut verification_key)?;
+        Ok(self.load_verification_key(&verification_key)?)
+    }
+
+    /// Computes the verification key from the proving key.
+    ///
+    /// Fails with [`BackendError::ProvingKeyNotInitialized`] unless a key was initialized by
+    /// [`AcirComposer::init_proving_key`], [`AcirComposer::create_proof`] or
+    /// [`AcirComposer::load_verification_key`]: on such a fresh composer the backend dereferences
+    /// a null CRS factory instead of throwing, which would crash the process.
+    pub fn init_verification_key(&self) -> Result<(), BackendError> {
+        let op = self.operation("acir_init_verification_key");
+        self.require_key(&op)?;
+        self.cached_verification_key.take();
+        ffi_call!(self; op, acir_init_verification_key(&self.ptr))?;
+        self.vk_initialized.set(true);
+        Ok(())
+    }
+
+    /// Serializes the verification key, computing it first if necessary, in the format
+    /// [`AcirComposer::load_verification_key`] accepts.
+    pub fn get_verification_key(&self) -> Result<Vec<u8>, BackendError> {
+        self.get_verification_key_buffer().map(OwnedBuffer::to_vec)
+    }
+
+    /// Like [`AcirComposer::get_verification_key`], but returns a [`VerificationKey`] that is
+    /// kept on the composer: later calls return clones sharing the same bytes without calling the
+    /// backend, until [`AcirComposer::load_verification_key`] or
+    /// [`AcirComposer::init_verification_key`] replaces the key.
+    pub fn verification_key(&self) -> Result<VerificationKey, BackendError> {
+        if let Some(verification_key) = &*self.cached_verification_key.borrow() {
+            return Ok(verification_key.clone());
         }
+        let verification_key = VerificationKey::from(self.get_verification_key()?);
+        *self.cached_verification_key.borrow_mut() = Some(verification_key.clone());
+        Ok(verification_key)
     }
 
-    pub fn get_verification_key(&self) -> Result<Vec<u8>, &'static str> {
+    /// Like [`AcirComposer::get_verification_key`], but returns the key in the buffer the backend
+    /// allocated instead of copying it, e.g. to pass `&vk[..]` straight to
+    /// [`AcirComposer::load_verification_key`].
+    pub fn get_verification_key_buffer(&self) -> Result<OwnedBuffer, BackendError> {
+        let op = self.operation("acir_get_verification_key");
+        self.require_key(&op)?;
         let mut out_ptr: *mut u8 = ptr::null_mut();
-        let error_msg_ptr = unsafe { acir_get_verification_key(&self.ptr, &mut out_ptr) };
-        if !error_msg_ptr.is_null() {
-            println!(
-                "C++ error: {}",
-                parse_c_str(error_msg_ptr).unwrap_or("Parsing c_str failed".to_string())
-            );
-        }
-        if out_ptr.is_null() {
-            Err("Failed to get verification key.")
-        } else {
-            let result = unsafe { Buffer::from_ptr(out_ptr)?.to_vec() };
-            Ok(result)
-        }
+        ffi_call!(self; op, acir_get_verification_key(&self.ptr, &mut out_ptr))?;
+        self.vk_initialized.set(true);
+        // Like the proof, the key is returned as a vector wrapped in another length prefix.
+        let buffer = unsafe { take_buffer(&op, out_ptr, MAX_VERIFICATION_KEY_LEN)? };
+        buffer
+            .into_vector()
+            .map_err(|reason| BackendError::MalformedOutput {
+                op,
+                reason: reason.to_string(),
+            })
     }
 
-    pub fn verify_proof(&self, proof: &[u8], is_recursive: bool) -> bool {
-        let mut result = false;
-        let error_msg_ptr =
-            unsafe { acir_verify_proof(&self.ptr, serialize_slice(proof).as_slice().as_ptr(), &is_recursive, &mut result) };
-        if !error_msg_ptr.is_null() {
-            println!(
-                "C++ error: {}",
-                parse_c_str(error_msg_ptr).unwrap_or("Parsing c_str failed".to_string())
-            );
+    /// Verifies `proof`, returning `Ok(false)` for an invalid proof and `Err` if the backend itself
+    /// failed (e.g. a malformed proof buffer).
+    ///
+    /// A verification key is computed on demand from the proving key if none was initialized or
+    /// loaded. Without either key this fails with [`BackendError::VerificationKeyNotInitialized`]
+    /// rather than letting the backend verify against a missing key.
+    ///
+    /// `mode` is the mode `proof` was created in. Pass `None` to use the one the proof recorded,
+    /// see [`Proof::mode`], or [`ProofMode::Standard`] if it recorded none. A mode that contradicts
+    /// the recorded one is refused with [`BackendError::ProofModeMismatch`].
+    pub fn verify_proof(
+        &self,
+        proof: &Proof,
+        mode: impl Into<Option<ProofMode>>,
+    ) -> Result<bool, BackendError> {
+        let mode = match (mode.into(), proof.mode()) {
+            (Some(requested), Some(recorded)) if requested != recorded => {
+                return Err(BackendError::ProofModeMismatch {
+                    op: self
+                        .operation("acir_verify_proof")
+                        .input("proof", proof.as_ref().len()),
+                    proof: recorded,
+                    requested,
+                });
+            }
+            (Some(mode), _) | (None, Some(mode)) => mode,
+            (None, None) => ProofMode::Standard,
+        };
+        self.verify_proof_bytes(proof.as_ref(), mode)
+    }
+
+    /// [`AcirComposer::verify_proof`] with the mode given as barretenberg's `is_recursive` flag.
+    #[deprecated(note = "pass a `ProofMode` to `AcirComposer::verify_proof`")]
+    pub fn verify_proof_legacy(
+        &self,
+        proof: &Proof,
+        is_recursive: bool,
+    ) -> Result<bool, BackendError> {
+        self.verify_proof(proof, ProofMode::from_is_recursive(is_recursive))
+    }
+
+    /// The verification key as the Solidity verifier lays it out in memory: the 32-byte words
+    /// [`AcirComposer::get_solidity_verifier`] stores at consecutive offsets of the key, in order,
+    /// e.g. to pass to a contract that takes the key as `bytes`.
+    ///
+    /// barretenberg only writes the key into the contract source, so the words are read back from
+    /// the `mstore(add(_vk, offset), value)` statements of the generated library. The inverse of
+    /// the root of unity, which the library stores outside the key, is not included.
+    pub fn get_verification_key_solidity_bytes(&self) -> Result<Vec<u8>, BackendError> {
+        let source = self.get_solidity_verifier()?;
+        solidity_verification_key(&source).map_err(|reason| BackendError::MalformedOutput {
+            op: self.operation("acir_get_solidity_verifier"),
+            reason,
+        })
+    }
+
+    /// Verifies each of `proofs` against this composer's verification key, in order.
+    ///
+    /// barretenberg has no batch verification entry point, so this makes one `acir_verify_proof`
+    /// call per proof. The key stays loaded in the composer between calls; if only a proving key
+    /// is set, the first call derives it and the rest reuse it. A failure verifying one proof does
+    /// not stop the others from being checked.
+    ///
+    /// Each proof is checked against `mode` as by [`AcirComposer::verify_proof`], so a proof that
+    /// recorded another mode fails with [`BackendError::ProofModeMismatch`].
+    pub fn verify_proofs(
+        &self,
+        proofs: &[Proof],
+        mode: impl Into<Option<ProofMode>>,
+    ) -> Vec<Result<bool, BackendError>> {
+        let mode = mode.into();
+        proofs
+            .iter()
+            .map(|proof| self.verify_proof(proof, mode))
+            .collect()
+    }
+
+    fn verify_proof_bytes(&self, proof: &[u8], mode: ProofMode) -> Result<bool, BackendError> {
+        let op = self
+            .operation("acir_verify_proof")
+            .input("proof", proof.len());
+        if !self.pk_initialized.get() {
+            self.require_verification_key(&op)?;
         }
-        result
+        let mut result = false;
+        ffi_call!(self; op, acir_verify_proof(
+            &self.ptr,
+            InputBuffer::new(proof).serialize().as_ptr(),
+            &mode.is_recursive(),
+            &mut result,
+        ))?;
+        self.vk_initialized.set(true);
+        Ok(result)
+    }
+
+    /// Renders the Solidity verifier contract for the current verification key.
+    ///
+    /// The source is returned like every other output buffer, as a length-prefixed string inside
+    /// a heap buffer owned by the caller, and is freed once copied.
+    pub fn get_solidity_verifier(&self) -> Result<String, BackendError> {
+        let op = self.operation("acir_get_solidity_verifier");
+        self.with_solidity_verifier(op, str::to_owned)
     }
 
-    pub fn get_solidity_verifier(&self) -> Result<String, &'static str> {
+    /// Like [`AcirComposer::get_solidity_verifier`], but writes the source straight from the
+    /// backend's buffer into `writer`, renamed and with the pragma and SPDX lines `options` ask
+    /// for.
+    ///
+    /// Options that would change more of the source than they name, such as a contract name that
+    /// is not an identifier, are refused with [`BackendError::MalformedInput`].
+    pub fn write_solidity_verifier<W: Write>(
+        &self,
+        writer: W,
+        options: &SolidityOptions,
+    ) -> Result<(), VerifierWriteError> {
+        let op = self.operation("acir_get_solidity_verifier");
+        check_input(&op, options.validate())?;
+        let written = self.with_solidity_verifier(op, |source| {
+            solidity::write_source(source, options, writer)
+        })?;
+        Ok(written?)
+    }
+
+    /// Renders the Solidity verifier and passes its source to `f` before the buffer is freed.
+    fn with_solidity_verifier<T>(
+        &self,
+        op: Operation,
+        f: impl FnOnce(&str) -> T,
+    ) -> Result<T, BackendError> {
+        self.require_verification_key(&op)?;
         let mut out_ptr: *mut u8 = ptr::null_mut();
-        let error_msg_ptr = unsafe { acir_get_solidity_verifier(&self.ptr, &mut out_ptr) };
-        if !error_msg_ptr.is_null() {
-            println!(
-                "C++ error: {}",
-                parse_c_str(error_msg_ptr).unwrap_or("Parsing c_str failed".to_string())
-            );
-        }
-        if out_ptr.is_null() {
-            Err("Failed to get solidity verifier.")
-        } else {
-            let verifier_string = unsafe {
-                CStr::from_ptr(out_ptr as *const c_char)
-                    .to_str()
-                    .unwrap()
-                    .to_string()
-            };
-            Ok(verifier_string)
-        }
+        ffi_call!(self; op, acir_get_solidity_verifier(&self.ptr, &mut out_ptr))?;
+        let buffer = unsafe { take_buffer(&op, out_ptr, MAX_SOLIDITY_VERIFIER_LEN)? };
+        let source = unwrap_vector(&op, &buffer)?;
+        let source = std::str::from_utf8(source).map_err(|_| BackendError::InvalidUtf8 { op })?;
+        Ok(f(source))
     }
 
+    /// Exports `proof` as field elements, the layout a recursion opcode reads it in, concatenated
+    /// behind their big-endian u32 count; [`AcirComposer::proof_as_fields`] returns them chunked.
+    ///
+    /// `num_inner_public_inputs` is the number of public inputs prepended to the proof. The backend
+    /// would split a proof with a different count at the wrong offsets, so the proof's length is
+    /// checked against it first and a mismatch is refused with [`BackendError::MalformedInput`].
     pub fn serialize_proof_into_fields(
         &self,
         proof: &[u8],
         num_inner_public_inputs: u32,
-    ) -> Result<Vec<u8>, &'static str> {
+    ) -> Result<Vec<u8>, BackendError> {
+        self.proof_fields(proof, num_inner_public_inputs)
+            .map(|(_, fields)| fields.to_vec())
+    }
+
+    /// Like [`AcirComposer::serialize_proof_into_fields`], but with the number of public inputs
+    /// read from the verification key of this composer's circuit instead of passed in.
+    ///
+    /// The verification key is computed from the proving key if it was not initialized yet.
+    pub fn serialize_proof_into_fields_for_circuit(
+        &self,
+        proof: &[u8],
+    ) -> Result<Vec<u8>, BackendError> {
+        let num_public_inputs = self.num_public_inputs()?;
+        self.serialize_proof_into_fields(proof, num_public_inputs)
+    }
+
+    /// Like [`AcirComposer::serialize_proof_into_fields`], but split into 32-byte field elements,
+    /// which are read from the backend's buffer rather than copied; [`FieldVec::to_vec`] copies
+    /// them into a `Vec<[u8; 32]>`, e.g. for a calldata encoder. A buffer that is not a whole
+    /// number of elements is refused with [`BackendError::Misaligned`].
+    pub fn proof_as_fields(
+        &self,
+        proof: &[u8],
+        num_inner_public_inputs: u32,
+    ) -> Result<FieldVec, BackendError> {
+        let (op, fields) = self.proof_fields(proof, num_inner_public_inputs)?;
+        fields
+            .into_field_vec()
+            .map_err(|err| buffer_error(&op, err))
+    }
+
+    fn proof_fields(
+        &self,
+        proof: &[u8],
+        num_inner_public_inputs: u32,
+    ) -> Result<(Operation, OwnedBuffer), BackendError> {
+        let op = self
+            .operation("acir_serialize_proof_into_fields")
+            .input("proof", proof.len());
+        let count = proof_public_input_count(proof).map_err(|err| err.to_string());
+        let count = count.and_then(|count| {
+            if count == num_inner_public_inputs {
+                Ok(())
+            } else {
+                Err(format!(
+                    "the proof holds {count} public inputs, not {num_inner_public_inputs}"
+                ))
+            }
+        });
+        check_input(&op, count)?;
         let mut out_ptr: *mut u8 = ptr::null_mut();
-        let error_msg_ptr = unsafe {
-            acir_serialize_proof_into_fields(
-                &self.ptr,
-                proof.as_ptr(),
-                &num_inner_public_inputs,
-                &mut out_ptr,
-            )
-        };
-        if !error_msg_ptr.is_null() {
-            println!(
-                "C++ error: {}",
-                parse_c_str(error_msg_ptr).unwrap_or("Parsing c_str failed".to_string())
-            );
-        }
-        if out_ptr.is_null() {
-            Err("Failed to serialize proof into fields.")
-        } else {
-            let result = unsafe { Buffer::from_ptr(out_ptr)?.to_vec() };
-            Ok(result)
+        ffi_call!(self; op, acir_serialize_proof_into_fields(
+            &self.ptr,
+            InputBuffer::new(proof).serialize().as_ptr(),
+            &num_inner_public_inputs,
+            &mut out_ptr,
+        ))?;
+        let fields = unsafe { take_buffer(&op, out_ptr, MAX_PROOF_FIELDS_LEN)? };
+        Ok((op, fields))
+    }
+
+    /// The number of public inputs of this composer's circuit, read from its verification key,
+    /// which is computed from the proving key unless it was already computed or loaded.
+    ///
+    /// Fails with [`BackendError::ProvingKeyNotInitialized`] if the composer holds neither key.
+    pub fn num_public_inputs(&self) -> Result<u32, BackendError> {
+        self.read_verification_key(VerificationKey::num_public_inputs)
+    }
+
+    /// The size of the evaluation subgroup the backend settled on for this composer's circuit,
+    /// after padding, read from its verification key as [`AcirComposer::num_public_inputs`] is.
+    ///
+    /// This can exceed the `subgroup` that [`get_circuit_sizes`] estimates from the constraint
+    /// system, and proving needs a CRS of more points than it. The backend exports no other sizes
+    /// of a live composer, so the gate counts of [`CircuitSizes`] are not available here.
+    pub fn circuit_size(&self) -> Result<u32, BackendError> {
+        self.read_verification_key(VerificationKey::circuit_size)
+    }
+
+    /// Reads a field of the verification key, computing the key first if necessary.
+    fn read_verification_key(
+        &self,
+        read: fn(&VerificationKey) -> Option<u32>,
+    ) -> Result<u32, BackendError> {
+        if !self.has_verification_key() {
+            self.init_verification_key()?;
         }
+        read(&self.verification_key()?).ok_or_else(|| BackendError::MalformedOutput {
+            op: self.operation("acir_get_verification_key"),
+            reason: "verification key is truncated".to_string(),
+        })
     }
 
+    /// Exports the verification key as field elements together with its hash, the inputs of a
+    /// recursion opcode verifying proofs of this circuit.
+    ///
+    /// The key's buffer is rejected with [`BackendError::Misaligned`] unless it holds a whole
+    /// number of field elements.
     pub fn serialize_verification_key_into_fields(
         &self,
-    ) -> Result<(Vec<u8>, Vec<u8>), &'static str> {
+    ) -> Result<VerificationKeyFields, BackendError> {
+        let (key_fields, key_hash) = self.verification_key_as_fields()?;
+        Ok(VerificationKeyFields {
+            key_fields: key_fields.to_vec(),
+            key_hash,
+        })
+    }
+
+    /// Like [`AcirComposer::serialize_verification_key_into_fields`], but with the field elements
+    /// read in place from the backend's buffer like [`AcirComposer::proof_as_fields`].
+    pub fn verification_key_as_fields(&self) -> Result<(FieldVec, [u8; 32]), BackendError> {
+        let (op, vkey, key_hash) = self.verification_key_fields()?;
+        let fields = vkey
+            .into_field_vec()
+            .map_err(|err| buffer_error(&op, err))?;
+        Ok((fields, key_hash))
+    }
+
+    /// The hash of the verification key, as returned alongside its fields by
+    /// [`AcirComposer::serialize_verification_key_into_fields`].
+    ///
+    /// The backend only computes the hash while exporting the fields, so this makes the same call
+    /// but frees the fields without copying them.
+    pub fn verification_key_hash(&self) -> Result<[u8; 32], BackendError> {
+        self.verification_key_fields()
+            .map(|(_, _, key_hash)| key_hash)
+    }
+
+    fn verification_key_fields(&self) -> Result<(Operation, OwnedBuffer, [u8; 32]), BackendError> {
+        let op = self.operation("acir_serialize_verification_key_into_fields");
+        self.require_verification_key(&op)?;
         let mut out_vkey_ptr: *mut u8 = ptr::null_mut();
-        let out_key_hash_ptr: *mut u8 = ptr::null_mut();
-        let error_msg_ptr = unsafe {
-            acir_serialize_verification_key_into_fields(
-                &self.ptr,
-                &mut out_vkey_ptr,
-                out_key_hash_ptr,
-            )
-        };
-        if !error_msg_ptr.is_null() {
-            println!(
-                "C++ error: {}",
-                parse_c_str(error_msg_ptr).unwrap_or("Parsing c_str failed".to_string())
-            );
+        // The key hash is a single field element written in place, not a heap buffer.
+        let mut key_hash = [0u8; 32];
+        ffi_call!(self; op, acir_serialize_verification_key_into_fields(
+            &self.ptr,
+            &mut out_vkey_ptr,
+            key_hash.as_mut_ptr(),
+        ))?;
+        let vkey = unsafe { take_buffer(&op, out_vkey_ptr, MAX_VERIFICATION_KEY_LEN)? };
+        Ok((op, vkey, key_hash))
+    }
+
+    /// [`crate::self_check::prove_and_verify_example`], with any error reported as `false`.
+    #[deprecated(note = "use `self_check::prove_and_verify_example`, which reports errors")]
+    pub fn simple_create_and_verify_proof() -> bool {
+        crate::self_check::prove_and_verify_example().unwrap_or(false)
+    }
+
+    /// Frees the underlying C++ composer, reporting whether its destructor failed.
+    ///
+    /// Dropping the composer frees it too, but can only discard such an error.
+    pub fn close(mut self) -> Result<(), BackendError> {
+        self.delete()
+    }
+
+    /// Replaces the underlying C++ composer with a fresh one of the same size hint, so a single
+    /// composer can prove one circuit after another.
+    ///
+    /// Everything tied to the old circuit is cleared: the keys, the recorded circuit sizes and the
+    /// constraint system bound by [`AcirComposer::set_constraint_system`]. The fresh composer is
+    /// created first, so if that fails this one is left as it was; an error freeing the old one is
+    /// reported after the swap.
+    pub fn reset(&mut self) -> Result<(), BackendError> {
+        let fresh = Self::with_max_size_hint(self.size_hint, self.size_hint)?;
+        mem::replace(self, fresh).close()
+    }
+
+    /// Internally frees the underlying ACIR composer. Once freed the pointer is cleared, so
+    /// further calls do nothing rather than free it twice.
+    pub(super) fn delete(&mut self) -> Result<(), BackendError> {
+        if self.ptr.is_null() {
+            return Ok(());
         }
-        if out_vkey_ptr.is_null() || out_key_hash_ptr.is_null() {
-            Err("Failed to serialize verification key into fields.")
+        let op = self.operation("acir_delete_acir_composer");
+        let result = ffi_call!(op, acir_delete_acir_composer(&self.ptr));
+        // The object is gone even if its destructor threw, so it must not be deleted again.
+        self.ptr = ptr::null_mut();
+        result
+    }
+
+    /// The C++ message of the most recent backend call on this composer, or `None` if that call
+    /// succeeded.
+    pub fn last_backend_error(&self) -> Option<String> {
+        self.last_error.borrow().clone()
+    }
+
+    /// Like [`check_error`], but also records the outcome for [`AcirComposer::last_backend_error`],
+    /// so every FFI call on the composer either replaces or clears the previous message.
+    fn check_error(
+        &self,
+        op: &Operation,
+        error_msg_ptr: *const c_char,
+    ) -> Result<(), BackendError> {
+        let result = check_error(op, error_msg_ptr);
+        *self.last_error.borrow_mut() = match &result {
+            Err(BackendError::Backend { message, .. }) => Some(message.clone()),
+            _ => None,
+        };
+        result
+    }
+
+    /// Records the sizes of the circuit the backend is about to build, for [`fmt::Debug`] and
+    /// [`AcirComposer::create_proof_with_stats`].
+    fn record_circuit_sizes(
+        &self,
+        op: &Operation,
+        serialized_constraint_system: &[u8],
+    ) -> Result<(), BackendError> {
+        let check = |op: &Operation, ret| self.check_error(op, ret);
+        let sizes = serialized_circuit_sizes(op, serialized_constraint_system, check)?;
+        self.circuit_sizes.set(Some(sizes));
+        Ok(())
+    }
+
+    /// Fails unless the backend holds a proving or verification key to compute or use the
+    /// verification key from.
+    fn require_key(&self, op: &Operation) -> Result<(), BackendError> {
+        if self.pk_initialized.get() || self.vk_initialized.get() {
+            Ok(())
         } else {
-            let vkey = unsafe { Buffer::from_ptr(out_vkey_ptr)?.to_vec() };
-            let key_hash = unsafe { Buffer::from_ptr(out_key_hash_ptr)?.to_vec() };
-            Ok((vkey, key_hash))
+            Err(BackendError::ProvingKeyNotInitialized { op: op.clone() })
         }
     }
 
-    pub fn simple_create_and_verify_proof() -> bool {
-        let mut result = false;
-        let error_msg_ptr =
-            unsafe { examples_simple_create_and_verify_proof(&mut result) };
-        if !error_msg_ptr.is_null() {
-            println!(
-                "C++ error: {}",
-                parse_c_str(error_msg_ptr).unwrap_or("Parsing c_str failed".to_string())
-            );
+    /// Fails unless a verification key was computed or loaded; the backend would dereference a
+    /// null key otherwise.
+    fn require_verification_key(&self, op: &Operation) -> Result<(), BackendError> {
+        if self.vk_initialized.get() {
+            Ok(())
+        } else {
+            Err(BackendError::VerificationKeyNotInitialized { op: op.clone() })
         }
-        result
     }
 
-    /// Internally frees the underlying ACIR composer.
-    fn delete(&self) {
-        let error_msg_ptr = unsafe { acir_delete_acir_composer(&self.ptr) };
-        if !error_msg_ptr.is_null() {
-            println!(
-                "C++ error: {}",
-                parse_c_str(error_msg_ptr).unwrap_or("Parsing c_str failed".to_string())
-            );
+    /// Describes a call to `name` on this composer, for error reporting.
+    pub(super) fn operation(&self, name: &'static str) -> Operation {
+        Operation::new(name).size_hint(self.size_hint)
+    }
+}
+
+/// Turns a failed [`validation`] check of an input to `op` into [`BackendError::MalformedInput`].
+pub(crate) fn check_input(op: &Operation, checked: Result<(), String>) -> Result<(), BackendError> {
+    checked.map_err(|reason| BackendError::MalformedInput {
+        op: op.clone(),
+        reason,
+    })
+}
+
+/// Takes ownership of the length-prefixed output buffer of the barretenberg call `op`, which must
+/// hold between 1 and `max_len` bytes.
+///
+/// # Safety
+/// `ptr` must be null or satisfy the requirements of [`OwnedBuffer::from_heap_ptr_bounded`].
+unsafe fn take_buffer(
+    op: &Operation,
+    ptr: *mut u8,
+    max_len: usize,
+) -> Result<OwnedBuffer, BackendError> {
+    OwnedBuffer::from_heap_ptr_bounded(ptr, max_len).map_err(|err| buffer_error(op, err))
+}
+
+/// Reports a problem with the output buffer of the barretenberg call `op`.
+fn buffer_error(op: &Operation, err: BufferError) -> BackendError {
+    let op = op.clone();
+    match err {
+        BufferError::Null => BackendError::FfiNull { op },
+        BufferError::TooLarge { len, max } => BackendError::BufferTooLarge { op, len, max },
+        BufferError::Misaligned { len } => BackendError::Misaligned { op, len },
+        BufferError::Empty | BufferError::Malformed(_) => BackendError::MalformedOutput {
+            op,
+            reason: err.to_string(),
+        },
+    }
+}
+
+/// Borrows the serialized byte vector or string nested in the output buffer of `op`.
+fn unwrap_vector<'a>(op: &Operation, buffer: &'a OwnedBuffer) -> Result<&'a [u8], BackendError> {
+    read_vector(buffer.as_slice()).map_err(|reason| BackendError::MalformedOutput {
+        op: op.clone(),
+        reason: reason.to_string(),
+    })
+}
+
+/// Reads the words of the verification key out of the Solidity verifier `source`.
+fn solidity_verification_key(source: &str) -> Result<Vec<u8>, String> {
+    let mut key = Vec::new();
+    for line in source.lines() {
+        let Some(statement) = line.trim_start().strip_prefix("mstore(add(_vk, ") else {
+            continue;
+        };
+        let malformed = || format!("malformed verification key statement {:?}", line.trim());
+        let (offset, rest) = statement.split_once("), ").ok_or_else(malformed)?;
+        let (value, _) = rest.split_once(')').ok_or_else(malformed)?;
+        let offset = usize::from_str_radix(offset.trim_start_matches("0x"), 16)
+            .map_err(|_| malformed())?;
+        if offset != key.len() {
+            return Err(format!(
+                "verification key word at offset {offset:#x}, expected {:#x}",
+                key.len()
+            ));
+        }
+        let digits = value.trim_start_matches("0x");
+        if digits.len() > 64 {
+            return Err(malformed());
         }
+        key.extend(hex::decode(format!("{digits:0>64}")).map_err(|_| malformed())?);
+    }
+    if key.is_empty() {
+        return Err("no verification key in the Solidity verifier".to_string());
+    }
+    Ok(key)
+}
+
+/// Shows which keys the composer holds rather than its raw pointer. `circuit_sizes` is only known
+/// once the composer has built a circuit, by [`AcirComposer::init_proving_key`] or while proving.
+impl fmt::Debug for AcirComposer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AcirComposer")
+            .field("size_hint", &self.size_hint)
+            .field("pk_initialized", &self.pk_initialized.get())
+            .field("vk_initialized", &self.vk_initialized.get())
+            .field("circuit_sizes", &self.circuit_sizes.get())
+            .finish()
     }
 }
 
 impl Drop for AcirComposer {
     fn drop(&mut self) {
-        self.delete();
+        // Nothing can be reported from here; `close` returns the error instead.
+        let _ = self.delete();
+    }
+}
+
+/// Errors from [`AcirComposer::load_verification_key_from_reader`].
+#[derive(Debug)]
+pub enum KeyReadError {
+    /// The key could not be read.
+    Io(io::Error),
+    /// The key is malformed or the backend rejected it.
+    Backend(BackendError),
+}
+
+impl fmt::Display for KeyReadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KeyReadError::Io(err) => write!(f, "failed to read the verification key: {err}"),
+            KeyReadError::Backend(err) => err.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for KeyReadError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            KeyReadError::Io(err) => Some(err),
+            KeyReadError::Backend(err) => Some(err),
+        }
+    }
+}
+
+impl From<io::Error> for KeyReadError {
+    fn from(err: io::Error) -> Self {
+        KeyReadError::Io(err)
+    }
+}
+
+impl From<BackendError> for KeyReadError {
+    fn from(err: BackendError) -> Self {
+        KeyReadError::Backend(err)
+    }
+}
+
+/// Errors from [`AcirComposer::create_proof_into`].
+#[derive(Debug)]
+pub enum ProofWriteError {
+    /// The proof could not be written.
+    Io(io::Error),
+    /// Proving failed.
+    Backend(BackendError),
+}
+
+impl fmt::Display for ProofWriteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProofWriteError::Io(err) => write!(f, "failed to write the proof: {err}"),
+            ProofWriteError::Backend(err) => err.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for ProofWriteError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ProofWriteError::Io(err) => Some(err),
+            ProofWriteError::Backend(err) => Some(err),
+        }
+    }
+}
+
+impl From<io::Error> for ProofWriteError {
+    fn from(err: io::Error) -> Self {
+        ProofWriteError::Io(err)
+    }
+}
+
+impl From<BackendError> for ProofWriteError {
+    fn from(err: BackendError) -> Self {
+        ProofWriteError::Backend(err)
+    }
+}
+
+/// Errors from [`AcirComposer::create_proof_from_witness_file`].
+#[cfg(feature = "mmap")]
+#[derive(Debug)]
+pub enum WitnessReadError {
+    /// The witness file could not be opened or mapped.
+    Io(io::Error),
+    /// Proving failed.
+    Backend(BackendError),
+}
+
+#[cfg(feature = "mmap")]
+impl fmt::Display for WitnessReadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WitnessReadError::Io(err) => write!(f, "failed to map the witness file: {err}"),
+            WitnessReadError::Backend(err) => err.fmt(f),
+        }
+    }
+}
+
+#[cfg(feature = "mmap")]
+impl std::error::Error for WitnessReadError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            WitnessReadError::Io(err) => Some(err),
+            WitnessReadError::Backend(err) => Some(err),
+        }
+    }
+}
+
+#[cfg(feature = "mmap")]
+impl From<io::Error> for WitnessReadError {
+    fn from(err: io::Error) -> Self {
+        WitnessReadError::Io(err)
+    }
+}
+
+#[cfg(feature = "mmap")]
+impl From<BackendError> for WitnessReadError {
+    fn from(err: BackendError) -> Self {
+        WitnessReadError::Backend(err)
+    }
+}
+
+/// Errors from [`AcirComposer::write_solidity_verifier`].
+#[derive(Debug)]
+pub enum VerifierWriteError {
+    /// The source could not be written.
+    Io(io::Error),
+    /// Rendering the verifier failed, or the options were refused.
+    Backend(BackendError),
+}
+
+impl fmt::Display for VerifierWriteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VerifierWriteError::Io(err) => write!(f, "failed to write the verifier: {err}"),
+            VerifierWriteError::Backend(err) => err.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for VerifierWriteError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            VerifierWriteError::Io(err) => Some(err),
+            VerifierWriteError::Backend(err) => Some(err),
+        }
+    }
+}
+
+impl From<io::Error> for VerifierWriteError {
+    fn from(err: io::Error) -> Self {
+        VerifierWriteError::Io(err)
+    }
+}
+
+impl From<BackendError> for VerifierWriteError {
+    fn from(err: BackendError) -> Self {
+        VerifierWriteError::Backend(err)
     }
 }
 
 /// Represents the sizes of various circuit components.
-#[derive(Default, Debug)]
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CircuitSizes {
+    /// Number of gates the constraint system itself produces.
     pub exact: u32,
+    /// Number of rows the circuit occupies: the gates and public inputs, or the lookup tables if
+    /// those are larger, plus the rows the backend reserves.
     pub total: u32,
+    /// Size of the evaluation subgroup, `total` rounded up to a power of two.
     pub subgroup: u32,
 }
 
+impl CircuitSizes {
+    /// `acir_get_circuit_sizes` stores each size with `htonl`, i.e. as big-endian bytes inside a
+    /// native `u32` slot, so every field must be converted back to host byte order.
+    fn into_host_order(self) -> Self {
+        CircuitSizes {
+            exact: u32::from_be(self.exact),
+            total: u32::from_be(self.total),
+            subgroup: u32::from_be(self.subgroup),
+        }
+    }
+}
+
+/// Proves `witness` satisfies `constraint_system` and exports the verification key for the proof.
+///
+/// The composer is sized from [`get_circuit_sizes`]; use [`prove_with_hint`] to skip that call
+/// when the subgroup size is already known.
+pub fn prove(
+    constraint_system: &[u8],
+    witness: &[u8],
+    mode: ProofMode,
+) -> Result<ProofArtifacts, BackendError> {
+    let sizes = get_circuit_sizes(constraint_system)?;
+    prove_with_hint(constraint_system, witness, mode, sizes.subgroup)
+}
+
+/// Like [`prove`], but creates the composer with `size_hint`, as [`AcirComposer::new`] does.
+pub fn prove_with_hint(
+    constraint_system: &[u8],
+    witness: &[u8],
+    mode: ProofMode,
+    size_hint: u32,
+) -> Result<ProofArtifacts, BackendError> {
+    let acir_composer = AcirComposer::new(size_hint)?;
+    let proof = acir_composer.create_proof(constraint_system, witness, mode)?;
+    let verification_key = acir_composer.get_verification_key()?;
+    Ok(ProofArtifacts {
+        proof,
+        verification_key,
+    })
+}
+
+/// Verifies `proof` against the verification key `vk`, for callers that only verify.
+///
+/// This creates a composer just for the call, loads `vk` into it and drops it afterwards, so
+/// concurrent calls share nothing but the CRS, which must already be initialized. Their backend
+/// calls still take turns, as described on [`AcirComposer`]. To verify many proofs against one
+/// key, create a verifier with [`AcirComposer::with_verification_key`] instead.
+pub fn verify(vk: &[u8], proof: &[u8], mode: ProofMode) -> Result<bool, BackendError> {
+    AcirComposer::verifier(vk)?.verify_proof_bytes(proof, mode)
+}
+
 /// Fetches the sizes for various circuit components using the provided constraint system buffer.
-pub fn get_circuit_sizes(constraint_system_buf: &[u8]) -> CircuitSizes {
+pub fn get_circuit_sizes(constraint_system_buf: &[u8]) -> Result<CircuitSizes, BackendError> {
+    let op = Operation::new("acir_get_circuit_sizes").input("cs", constraint_system_buf.len());
+    check_input(&op, validation::constraint_system(constraint_system_buf))?;
+    let serialized = InputBuffer::new(constraint_system_buf).serialize();
+    serialized_circuit_sizes(&op, &serialized, check_error)
+}
+
+/// [`get_circuit_sizes`] of a constraint system already validated and serialized with its length
+/// prefix, with the outcome reported against `op` by `check`.
+fn serialized_circuit_sizes(
+    op: &Operation,
+    serialized_constraint_system: &[u8],
+    check: impl Fn(&Operation, *const c_char) -> Result<(), BackendError>,
+) -> Result<CircuitSizes, BackendError> {
     let mut ret = CircuitSizes::default();
-    let error_msg_ptr = unsafe {
-        acir_get_circuit_sizes(
-            serialize_slice(constraint_system_buf).as_slice().as_ptr(),
-            &mut ret.exact,
-            &mut ret.total,
-            &mut ret.subgroup,
-        )
-    };
-    if !error_msg_ptr.is_null() {
-        println!(
-            "C++ error: {}",
-            parse_c_str(error_msg_ptr).unwrap_or("Parsing c_str failed".to_string())
-        );
-    }
-    ret.exact = u32::from_be(ret.exact);
-    ret.subgroup = u32::from_be(ret.subgroup);
-    ret.total = u32::from_be(ret.total);
-    ret
+    let (result, _) = ffi_call!(@call check, op, acir_get_circuit_sizes(
+        serialized_constraint_system.as_ptr(),
+        &mut ret.exact,
+        &mut ret.total,
+        &mut ret.subgroup
+    ));
+    result?;
+    let ret = ret.into_host_order();
+    #[cfg(feature = "tracing")]
+    tracing::debug!(
+        exact = ret.exact,
+        total = ret.total,
+        subgroup = ret.subgroup,
+        "acir_get_circuit_sizes"
+    );
+    Ok(ret)
 }
 
 
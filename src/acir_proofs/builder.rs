@@ -0,0 +1,81 @@
+use super::acir_composer::{get_circuit_sizes, AcirComposer, DEFAULT_MAX_SIZE_HINT};
+use crate::error::BackendError;
+
+/// The proving system an [`AcirComposer`] builds circuits for.
+///
+/// The bundled barretenberg only exposes an UltraPlonk composer through its C API, so that is the
+/// only variant for now; others will be added as the library gains entry points for them.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Backend {
+    /// UltraPlonk, with lookup tables and custom gates. This is what [`AcirComposer::new`] uses.
+    #[default]
+    UltraPlonk,
+}
+
+/// Configures and constructs an [`AcirComposer`].
+///
+/// ```ignore
+/// let composer = AcirComposerBuilder::new()
+///     .size_hint(sizes.subgroup)
+///     .backend(Backend::UltraPlonk)
+///     .build()?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct AcirComposerBuilder {
+    size_hint: u32,
+    max_size_hint: u32,
+    backend: Backend,
+}
+
+impl AcirComposerBuilder {
+    /// Starts a builder for the default [`Backend`] with no size hint set.
+    pub fn new() -> Self {
+        AcirComposerBuilder {
+            size_hint: 0,
+            max_size_hint: DEFAULT_MAX_SIZE_HINT,
+            backend: Backend::default(),
+        }
+    }
+
+    /// Starts a builder sized for `constraint_system_buf`, with the `subgroup` size
+    /// [`get_circuit_sizes`] reports for it as the size hint.
+    pub fn from_circuit(constraint_system_buf: &[u8]) -> Result<Self, BackendError> {
+        let sizes = get_circuit_sizes(constraint_system_buf)?;
+        Ok(Self::new().size_hint(sizes.subgroup))
+    }
+
+    /// Sets the size hint, in gates, as accepted by [`AcirComposer::new`]. It has no default, and
+    /// [`AcirComposerBuilder::build`] rejects a builder without one.
+    pub fn size_hint(mut self, size_hint: u32) -> Self {
+        self.size_hint = size_hint;
+        self
+    }
+
+    /// Sets the largest size hint `build` accepts, [`DEFAULT_MAX_SIZE_HINT`] unless overridden.
+    pub fn max_size_hint(mut self, max_size_hint: u32) -> Self {
+        self.max_size_hint = max_size_hint;
+        self
+    }
+
+    /// Selects the proving system.
+    pub fn backend(mut self, backend: Backend) -> Self {
+        self.backend = backend;
+        self
+    }
+
+    /// Constructs the composer, failing the same way [`AcirComposer::with_max_size_hint`] does.
+    pub fn build(self) -> Result<AcirComposer, BackendError> {
+        match self.backend {
+            Backend::UltraPlonk => {
+                AcirComposer::with_max_size_hint(self.size_hint, self.max_size_hint)
+            }
+        }
+    }
+}
+
+impl Default for AcirComposerBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
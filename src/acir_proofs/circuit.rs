@@ -0,0 +1,41 @@
+use std::sync::Arc;
+
+use super::acir_composer::check_input;
+use crate::buffer::InputBuffer;
+use crate::error::{BackendError, Operation};
+use crate::validation;
+
+/// A constraint system validated and serialized once, to prove any number of witnesses of the
+/// same circuit with [`super::acir_composer::AcirComposer::prove_witness`].
+///
+/// Each composer can only create a single proof, so a prover handling many witnesses still needs a
+/// composer per proof; cloning a `PreparedCircuit` into each only bumps a reference count, where
+/// [`super::acir_composer::AcirComposer::create_proof`] validates and copies the constraint system
+/// every time.
+#[derive(Debug, Clone)]
+pub struct PreparedCircuit {
+    /// The constraint system behind the length prefix `acir_create_proof` reads it with.
+    serialized: Arc<[u8]>,
+}
+
+impl PreparedCircuit {
+    /// Checks `constraint_system_buf` the way [`super::acir_composer::AcirComposer::create_proof`]
+    /// does and serializes it.
+    pub fn new(constraint_system_buf: &[u8]) -> Result<Self, BackendError> {
+        let op = Operation::new("acir_create_proof").input("cs", constraint_system_buf.len());
+        check_input(&op, validation::constraint_system(constraint_system_buf))?;
+        Ok(PreparedCircuit {
+            serialized: InputBuffer::new(constraint_system_buf).serialize().into(),
+        })
+    }
+
+    /// The constraint system, without its length prefix.
+    pub fn constraint_system(&self) -> &[u8] {
+        &self.serialized[4..]
+    }
+
+    /// The constraint system with its length prefix, as passed to the backend.
+    pub(crate) fn serialized(&self) -> &[u8] {
+        &self.serialized
+    }
+}
@@ -1,4 +1,12 @@
 pub mod acir_composer;
+pub mod builder;
+pub mod circuit;
+pub mod proof;
+pub mod prover;
+pub mod recursion;
+pub mod solidity;
+pub mod verification_key;
+pub mod verifier;
 
 #[cfg(test)]
 pub mod test;
@@ -0,0 +1,258 @@
+use std::fmt;
+use std::time::Duration;
+
+use super::acir_composer::CircuitSizes;
+use crate::ct::ct_eq;
+
+/// Size in bytes of a non-recursive UltraPlonk proof without any public inputs.
+const PROOF_SIZE_WITHOUT_PUBLIC_INPUTS: usize = 2144;
+
+/// Reads the number of public inputs prepended to a serialized UltraPlonk proof.
+///
+/// The proof carries no count of its own: like the backend's verifier, this derives it from the
+/// length, which must be that of a proof without public inputs plus a whole number of 32-byte
+/// field elements. The result can be passed to
+/// [`super::acir_composer::AcirComposer::serialize_proof_into_fields`].
+pub fn proof_public_input_count(proof: &[u8]) -> Result<u32, ProofLengthError> {
+    let Some(public_inputs_len) = proof.len().checked_sub(PROOF_SIZE_WITHOUT_PUBLIC_INPUTS) else {
+        return Err(ProofLengthError::TooShort { len: proof.len() });
+    };
+    if public_inputs_len % 32 != 0 {
+        return Err(ProofLengthError::PartialFieldElement { public_inputs_len });
+    }
+    let num = public_inputs_len / 32;
+    u32::try_from(num).map_err(|_| ProofLengthError::TooManyPublicInputs { num })
+}
+
+/// Errors from [`proof_public_input_count`]: the length is not that of an UltraPlonk proof.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProofLengthError {
+    /// The proof is shorter than one without public inputs.
+    TooShort { len: usize },
+    /// The bytes in front of the proof are not a whole number of 32-byte field elements.
+    PartialFieldElement { public_inputs_len: usize },
+    /// The proof holds more public inputs than fit in a `u32`.
+    TooManyPublicInputs { num: usize },
+}
+
+impl fmt::Display for ProofLengthError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProofLengthError::TooShort { len } => write!(
+                f,
+                "a proof takes at least {PROOF_SIZE_WITHOUT_PUBLIC_INPUTS} bytes, got {len}"
+            ),
+            ProofLengthError::PartialFieldElement { public_inputs_len } => write!(
+                f,
+                "{public_inputs_len} bytes of public inputs are not a whole number of field \
+                 elements"
+            ),
+            ProofLengthError::TooManyPublicInputs { num } => {
+                write!(f, "{num} public inputs do not fit in a u32")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ProofLengthError {}
+
+/// How a proof is created and verified: for a native or Solidity verifier, or for a recursion
+/// opcode inside another circuit. A proof only verifies in the mode it was created in.
+///
+/// More modes may be added as barretenberg gains proving schemes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[non_exhaustive]
+pub enum ProofMode {
+    /// A proof for a native or Solidity verifier.
+    #[default]
+    Standard,
+    /// A proof whose transcript is hashed so that a recursion opcode can verify it cheaply.
+    Recursive,
+}
+
+impl ProofMode {
+    /// The `is_recursive` flag barretenberg takes for this mode.
+    pub fn is_recursive(self) -> bool {
+        matches!(self, ProofMode::Recursive)
+    }
+
+    pub(crate) fn from_is_recursive(is_recursive: bool) -> Self {
+        if is_recursive {
+            ProofMode::Recursive
+        } else {
+            ProofMode::Standard
+        }
+    }
+}
+
+impl fmt::Display for ProofMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ProofMode::Standard => "standard",
+            ProofMode::Recursive => "recursive",
+        })
+    }
+}
+
+/// A serialized proof produced by [`super::acir_composer::AcirComposer::create_proof`].
+///
+/// The public inputs of the circuit are prepended to the proof as 32-byte big-endian field
+/// elements.
+///
+/// A proof created by a composer records its [`ProofMode`], which
+/// [`super::acir_composer::AcirComposer::verify_proof`] defaults to. The bytes do not encode it,
+/// so a proof built from bytes has none until [`Proof::with_mode`] sets it.
+///
+/// Proofs compare in constant time, see [`ct_eq`]; only their bytes are compared.
+#[derive(Debug, Clone)]
+pub struct Proof {
+    bytes: Vec<u8>,
+    mode: Option<ProofMode>,
+}
+
+impl Proof {
+    pub(crate) fn new(bytes: Vec<u8>, mode: ProofMode) -> Self {
+        Proof {
+            bytes,
+            mode: Some(mode),
+        }
+    }
+
+    /// The mode the proof was created in, if known.
+    pub fn mode(&self) -> Option<ProofMode> {
+        self.mode
+    }
+
+    /// Records the mode the proof was created in, e.g. after decoding it.
+    pub fn with_mode(mut self, mode: ProofMode) -> Self {
+        self.mode = Some(mode);
+        self
+    }
+
+    /// Returns the first `num` field elements of the proof, i.e. its public inputs.
+    ///
+    /// Fewer than `num` elements are returned if the proof is too short to hold them.
+    pub fn public_inputs(&self, num: u32) -> Vec<[u8; 32]> {
+        self.bytes
+            .chunks_exact(32)
+            .take(num as usize)
+            .map(|chunk| chunk.try_into().unwrap())
+            .collect()
+    }
+
+    /// Splits the proof into its first `num` field elements, its public inputs, and the proof
+    /// proper, e.g. to pass them separately to the Solidity verifier.
+    ///
+    /// Like [`Proof::public_inputs`], fewer than `num` elements are split off if the proof is too
+    /// short to hold them. [`Proof::from_parts`] reassembles the pieces.
+    pub fn split_public_inputs(&self, num: u32) -> (Vec<[u8; 32]>, Vec<u8>) {
+        let public_inputs = self.public_inputs(num);
+        (public_inputs.clone(), self.bytes[public_inputs.len() * 32..].to_vec())
+    }
+
+    /// Prepends `public_inputs` to `proof`, undoing [`Proof::split_public_inputs`].
+    pub fn from_parts(public_inputs: &[[u8; 32]], proof: &[u8]) -> Self {
+        Proof::from([public_inputs.concat().as_slice(), proof].concat())
+    }
+
+    /// The number of public inputs prepended to the proof, see [`proof_public_input_count`].
+    pub fn num_public_inputs(&self) -> Result<u32, ProofLengthError> {
+        proof_public_input_count(&self.bytes)
+    }
+
+    /// Consumes the proof, returning its raw bytes.
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+
+    /// Encodes the proof as a lowercase hex string, see [`crate::encoding::to_hex`].
+    #[cfg(feature = "encoding")]
+    pub fn to_hex(&self) -> String {
+        crate::encoding::to_hex(&self.bytes)
+    }
+
+    /// Decodes a proof from hex, see [`crate::encoding::from_hex`].
+    #[cfg(feature = "encoding")]
+    pub fn from_hex(hex_string: &str) -> Result<Self, crate::encoding::DecodeError> {
+        crate::encoding::from_hex(hex_string).map(Proof::from)
+    }
+
+    /// Encodes the proof as base64, see [`crate::encoding::to_base64`].
+    #[cfg(feature = "encoding")]
+    pub fn to_base64(&self) -> String {
+        crate::encoding::to_base64(&self.bytes)
+    }
+
+    /// Decodes a proof from base64, see [`crate::encoding::from_base64`].
+    #[cfg(feature = "encoding")]
+    pub fn from_base64(base64_string: &str) -> Result<Self, crate::encoding::DecodeError> {
+        crate::encoding::from_base64(base64_string).map(Proof::from)
+    }
+}
+
+impl PartialEq for Proof {
+    fn eq(&self, other: &Self) -> bool {
+        ct_eq(&self.bytes, &other.bytes)
+    }
+}
+
+impl Eq for Proof {}
+
+impl AsRef<[u8]> for Proof {
+    fn as_ref(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+impl From<Vec<u8>> for Proof {
+    fn from(bytes: Vec<u8>) -> Self {
+        Proof { bytes, mode: None }
+    }
+}
+
+impl From<Proof> for Vec<u8> {
+    fn from(proof: Proof) -> Self {
+        proof.bytes
+    }
+}
+
+/// Proofs serialize as a lowercase hex string of their bytes, without a `0x` prefix, and without
+/// their [`ProofMode`].
+#[cfg(feature = "serde")]
+impl serde::Serialize for Proof {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&hex::encode(&self.bytes))
+    }
+}
+
+/// Accepts the hex string written by [`Proof`]'s `Serialize` impl, in either case.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Proof {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let hex_string = <std::borrow::Cow<'de, str>>::deserialize(deserializer)?;
+        hex::decode(hex_string.as_ref())
+            .map(Proof::from)
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+/// Measurements taken by [`super::acir_composer::AcirComposer::create_proof_with_stats`].
+#[derive(Debug, Clone)]
+pub struct ProofStats {
+    /// Wall-clock time spent in the `acir_create_proof` call.
+    pub duration: Duration,
+    /// Length of the proof in bytes.
+    pub proof_len: usize,
+    /// Sizes of the circuit the proof was created for.
+    pub circuit_sizes: CircuitSizes,
+    /// Number of public inputs prepended to the proof.
+    pub num_public_inputs: u32,
+}
+
+/// A proof together with the verification key that checks it, as returned by
+/// [`super::acir_composer::prove`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProofArtifacts {
+    pub proof: Proof,
+    pub verification_key: Vec<u8>,
+}
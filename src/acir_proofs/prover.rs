@@ -0,0 +1,68 @@
+use super::acir_composer::AcirComposer;
+use super::proof::{Proof, ProofMode};
+use super::verification_key::VerificationKey;
+use super::verifier::AcirVerifier;
+use crate::error::BackendError;
+
+/// The proving half of an [`AcirComposer`]: it builds a circuit, computes its proving key and
+/// proves a witness, and can then hand its verification key over to an [`AcirVerifier`].
+///
+/// Like the composer it wraps, a prover creates a single proof.
+#[derive(Debug)]
+pub struct AcirProver {
+    composer: AcirComposer,
+}
+
+impl AcirProver {
+    /// Creates a prover, see [`AcirComposer::new`].
+    pub fn new(size_hint: u32) -> Result<Self, BackendError> {
+        AcirComposer::new(size_hint).map(|composer| AcirProver { composer })
+    }
+
+    /// See [`AcirComposer::init_proving_key`].
+    pub fn init_proving_key(&self, constraint_system_buf: &[u8]) -> Result<(), BackendError> {
+        self.composer.init_proving_key(constraint_system_buf)
+    }
+
+    /// See [`AcirComposer::create_proof`].
+    pub fn create_proof(
+        &self,
+        constraint_system_buf: &[u8],
+        witness: &[u8],
+        mode: ProofMode,
+    ) -> Result<Proof, BackendError> {
+        self.composer
+            .create_proof(constraint_system_buf, witness, mode)
+    }
+
+    /// See [`AcirComposer::verification_key`].
+    pub fn verification_key(&self) -> Result<VerificationKey, BackendError> {
+        self.composer.verification_key()
+    }
+
+    /// See [`AcirComposer::num_public_inputs`].
+    pub fn num_public_inputs(&self) -> Result<u32, BackendError> {
+        self.composer.num_public_inputs()
+    }
+
+    /// See [`AcirComposer::circuit_size`].
+    pub fn circuit_size(&self) -> Result<u32, BackendError> {
+        self.composer.circuit_size()
+    }
+
+    /// Turns the prover into a verifier for the circuit it built, computing the verification key
+    /// from the proving key unless that was already done.
+    ///
+    /// Fails with [`BackendError::ProvingKeyNotInitialized`] if the prover has no proving key yet.
+    pub fn into_verifier(self) -> Result<AcirVerifier, BackendError> {
+        if !self.composer.has_verification_key() {
+            self.composer.init_verification_key()?;
+        }
+        Ok(AcirVerifier::from_composer(self.composer))
+    }
+
+    /// See [`AcirComposer::close`].
+    pub fn close(self) -> Result<(), BackendError> {
+        self.composer.close()
+    }
+}
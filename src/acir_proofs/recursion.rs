@@ -0,0 +1,189 @@
+use super::acir_composer::AcirComposer;
+use super::proof::Proof;
+use crate::error::BackendError;
+
+/// Number of field elements in the aggregation object a recursion opcode takes as input and
+/// produces as output: two G1 points, each coordinate split into four 68-bit limbs.
+pub const AGGREGATION_OBJECT_SIZE: usize = 16;
+
+/// The witness values a recursion opcode needs to verify an inner proof inside an outer circuit.
+///
+/// barretenberg computes the aggregation object itself inside the outer circuit; its C API has no
+/// entry point that aggregates outside of proving. These are the inner proof and verification
+/// key in the layout that opcode expects, to be assigned to the witnesses it refers to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecursionInputs {
+    /// The public inputs of the inner proof, followed by its commitments and evaluations in
+    /// transcript order. Each commitment takes four elements, `x_lo`, `x_hi`, `y_lo` and `y_hi`,
+    /// holding the low 136 and high 118 bits of its coordinates.
+    pub proof: Vec<[u8; 32]>,
+    /// The evaluation domain root, size and generator, the circuit size, the number of public
+    /// inputs, whether the inner circuit itself contains a recursive proof and the
+    /// [`AGGREGATION_OBJECT_SIZE`] public input indices of its aggregation object, followed by
+    /// every selector and permutation commitment split into four elements like the proof's.
+    pub verification_key: Vec<[u8; 32]>,
+    /// Hash of the verification key, which the outer circuit checks the key fields against.
+    pub key_hash: [u8; 32],
+}
+
+/// The proof, verification key and key hash a Noir circuit passes to `std::verify_proof` to
+/// verify an inner proof, as returned by [`AcirComposer::get_recursive_artifacts`].
+///
+/// With the `serde` feature it serializes as the arguments of that call, e.g. for a `Prover.toml`:
+/// `verification_key`, `proof` and `public_inputs` as arrays of `0x`-prefixed hex field elements
+/// and `key_hash` as a single one. `std::verify_proof` takes the public inputs separately, so they
+/// are split off the front of the proof.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecursiveArtifacts {
+    /// The proof as field elements, starting with its public inputs, see
+    /// [`RecursionInputs::proof`].
+    pub proof_fields: Vec<[u8; 32]>,
+    /// The verification key as field elements, see [`RecursionInputs::verification_key`].
+    pub vk_fields: Vec<[u8; 32]>,
+    /// Hash of the verification key.
+    pub vk_hash: [u8; 32],
+    /// The number of public inputs `proof_fields` starts with.
+    pub num_public_inputs: u32,
+}
+
+impl RecursiveArtifacts {
+    /// The public inputs at the front of [`RecursiveArtifacts::proof_fields`].
+    pub fn public_inputs(&self) -> &[[u8; 32]] {
+        &self.proof_fields[..self.public_inputs_len()]
+    }
+
+    /// The rest of [`RecursiveArtifacts::proof_fields`], without the public inputs.
+    pub fn proof_without_public_inputs(&self) -> &[[u8; 32]] {
+        &self.proof_fields[self.public_inputs_len()..]
+    }
+
+    fn public_inputs_len(&self) -> usize {
+        (self.num_public_inputs as usize).min(self.proof_fields.len())
+    }
+}
+
+impl AcirComposer {
+    /// Exports `proof` and this composer's verification key as the inputs of a recursion opcode.
+    ///
+    /// `proof` must have been created by this composer with `is_recursive` set, whose proving key
+    /// is also used to compute the verification key if it was not initialized yet.
+    pub fn recursion_inputs(&self, proof: &Proof) -> Result<RecursionInputs, BackendError> {
+        let num_public_inputs = proof.num_public_inputs().map_err(|err| {
+            BackendError::MalformedInput {
+                op: self
+                    .operation("acir_serialize_proof_into_fields")
+                    .input("proof", proof.as_ref().len()),
+                reason: err.to_string(),
+            }
+        })?;
+        let artifacts = self.get_recursive_artifacts(proof.as_ref(), num_public_inputs)?;
+        Ok(RecursionInputs {
+            proof: artifacts.proof_fields,
+            verification_key: artifacts.vk_fields,
+            key_hash: artifacts.vk_hash,
+        })
+    }
+
+    /// Exports `proof`, which starts with `num_public_inputs` public inputs, together with this
+    /// composer's verification key and its hash, as Noir's `std::verify_proof` takes them.
+    ///
+    /// As for [`AcirComposer::recursion_inputs`], the verification key is computed if it was not
+    /// initialized yet. A proof whose public inputs do not match `num_public_inputs`, or the
+    /// circuit of the verification key, is refused with [`BackendError::MalformedInput`].
+    pub fn get_recursive_artifacts(
+        &self,
+        proof: &[u8],
+        num_public_inputs: u32,
+    ) -> Result<RecursiveArtifacts, BackendError> {
+        let proof_fields = self.proof_as_fields(proof, num_public_inputs)?.to_vec();
+        let circuit_public_inputs = self.num_public_inputs()?;
+        if circuit_public_inputs != num_public_inputs {
+            return Err(BackendError::MalformedInput {
+                op: self
+                    .operation("acir_serialize_proof_into_fields")
+                    .input("proof", proof.len()),
+                reason: format!(
+                    "the proof holds {num_public_inputs} public inputs, the circuit of the \
+                     verification key {circuit_public_inputs}"
+                ),
+            });
+        }
+        let vk = self.serialize_verification_key_into_fields()?;
+        Ok(RecursiveArtifacts {
+            proof_fields,
+            vk_fields: vk.key_fields,
+            vk_hash: vk.key_hash,
+            num_public_inputs,
+        })
+    }
+}
+
+/// The arguments of `std::verify_proof`, each field element a `0x`-prefixed hex string.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct VerifyProofArguments {
+    verification_key: Vec<String>,
+    proof: Vec<String>,
+    public_inputs: Vec<String>,
+    key_hash: String,
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for RecursiveArtifacts {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let hex_fields = |fields: &[[u8; 32]]| fields.iter().map(field_hex).collect();
+        VerifyProofArguments {
+            verification_key: hex_fields(&self.vk_fields),
+            proof: hex_fields(self.proof_without_public_inputs()),
+            public_inputs: hex_fields(self.public_inputs()),
+            key_hash: field_hex(&self.vk_hash),
+        }
+        .serialize(serializer)
+    }
+}
+
+/// Accepts the arguments written by [`RecursiveArtifacts`]'s `Serialize` impl. Field elements may
+/// omit leading zeros, as Noir writes them.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for RecursiveArtifacts {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let arguments = VerifyProofArguments::deserialize(deserializer)?;
+        let parse_fields = |fields: &[String]| {
+            fields
+                .iter()
+                .map(|field| parse_field(field))
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(serde::de::Error::custom)
+        };
+        let num_public_inputs = u32::try_from(arguments.public_inputs.len())
+            .map_err(|_| serde::de::Error::custom("too many public inputs"))?;
+        let mut proof_fields = parse_fields(&arguments.public_inputs)?;
+        proof_fields.extend(parse_fields(&arguments.proof)?);
+        Ok(RecursiveArtifacts {
+            proof_fields,
+            vk_fields: parse_fields(&arguments.verification_key)?,
+            vk_hash: parse_field(&arguments.key_hash).map_err(serde::de::Error::custom)?,
+            num_public_inputs,
+        })
+    }
+}
+
+#[cfg(feature = "serde")]
+fn field_hex(field: &[u8; 32]) -> String {
+    format!("0x{}", hex::encode(field))
+}
+
+/// Parses a `0x`-prefixed hex field element of at most 32 bytes.
+#[cfg(feature = "serde")]
+fn parse_field(field: &str) -> Result<[u8; 32], String> {
+    let digits = field
+        .strip_prefix("0x")
+        .ok_or_else(|| format!("field element {field:?} does not start with 0x"))?;
+    if digits.len() > 64 {
+        return Err(format!("field element {field:?} is longer than 32 bytes"));
+    }
+    let padded = format!("{digits:0>64}");
+    let mut bytes = [0u8; 32];
+    hex::decode_to_slice(padded, &mut bytes).map_err(|err| format!("{field:?}: {err}"))?;
+    Ok(bytes)
+}
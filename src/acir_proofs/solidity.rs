@@ -0,0 +1,133 @@
+use std::io::{self, Write};
+
+/// The name barretenberg gives the verification key library it generates.
+pub const DEFAULT_CONTRACT_NAME: &str = "UltraVerificationKey";
+/// The version requirement of the generated `pragma solidity` line.
+pub const DEFAULT_SOLIDITY_PRAGMA: &str = ">=0.8.4";
+/// The license of the generated `SPDX-License-Identifier` line.
+pub const DEFAULT_SPDX_LICENSE: &str = "Apache-2.0";
+
+const SPDX_PREFIX: &str = "// SPDX-License-Identifier:";
+const PRAGMA_PREFIX: &str = "pragma solidity ";
+
+/// How [`super::acir_composer::AcirComposer::write_solidity_verifier`] adjusts the Solidity
+/// verifier barretenberg generates. The default leaves it unchanged.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SolidityOptions {
+    /// Name of the library, in place of [`DEFAULT_CONTRACT_NAME`].
+    pub contract_name: String,
+    /// Version requirement of the `pragma solidity` line, e.g. `^0.8.19`.
+    pub solidity_pragma: String,
+    /// License of the `SPDX-License-Identifier` line, or `None` to drop the line.
+    pub spdx_license: Option<String>,
+}
+
+impl Default for SolidityOptions {
+    fn default() -> Self {
+        SolidityOptions {
+            contract_name: DEFAULT_CONTRACT_NAME.to_owned(),
+            solidity_pragma: DEFAULT_SOLIDITY_PRAGMA.to_owned(),
+            spdx_license: Some(DEFAULT_SPDX_LICENSE.to_owned()),
+        }
+    }
+}
+
+impl SolidityOptions {
+    /// Checks every option can be written into the source without changing anything but itself:
+    /// the name must be an identifier, and neither the pragma nor the license may end its line.
+    pub(crate) fn validate(&self) -> Result<(), String> {
+        if !is_identifier(&self.contract_name) {
+            return Err(format!(
+                "contract name {:?} is not a Solidity identifier",
+                self.contract_name
+            ));
+        }
+        if self.solidity_pragma.is_empty() || self.solidity_pragma.contains([';', '\n', '\r']) {
+            return Err(format!(
+                "solidity pragma {:?} is not a version requirement",
+                self.solidity_pragma
+            ));
+        }
+        if let Some(license) = &self.spdx_license {
+            if license.is_empty() || license.contains(['\n', '\r']) {
+                return Err(format!(
+                    "SPDX license {license:?} is not a license expression"
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Writes the generated `source` to `writer` line by line, adjusted as `options` ask.
+///
+/// The SPDX and pragma lines are replaced whole. The library is renamed wherever its name appears
+/// as an identifier, but not inside a longer identifier, a string literal or a comment.
+pub(crate) fn write_source<W: Write>(
+    source: &str,
+    options: &SolidityOptions,
+    mut writer: W,
+) -> io::Result<()> {
+    for line in source.split_inclusive('\n') {
+        let newline = if line.ends_with('\n') { "\n" } else { "" };
+        if line.starts_with(SPDX_PREFIX) {
+            if let Some(license) = &options.spdx_license {
+                write!(writer, "{SPDX_PREFIX} {license}{newline}")?;
+            }
+        } else if line.starts_with(PRAGMA_PREFIX) {
+            write!(
+                writer,
+                "{PRAGMA_PREFIX}{};{newline}",
+                options.solidity_pragma
+            )?;
+        } else {
+            write_renamed(line, &options.contract_name, &mut writer)?;
+        }
+    }
+    writer.flush()
+}
+
+/// Writes `line` with every identifier [`DEFAULT_CONTRACT_NAME`] in its code replaced by `name`.
+fn write_renamed<W: Write>(line: &str, name: &str, writer: &mut W) -> io::Result<()> {
+    let bytes = line.as_bytes();
+    let mut copied = 0;
+    let mut i = 0;
+    let mut quote = None;
+    while i < bytes.len() {
+        let byte = bytes[i];
+        match quote {
+            Some(_) if byte == b'\\' => i += 1,
+            Some(q) if byte == q => quote = None,
+            Some(_) => {}
+            None if byte == b'"' || byte == b'\'' => quote = Some(byte),
+            None if bytes[i..].starts_with(b"//") => break,
+            None if bytes[i..].starts_with(DEFAULT_CONTRACT_NAME.as_bytes())
+                && (i == 0 || !is_identifier_byte(bytes[i - 1]))
+                && !bytes
+                    .get(i + DEFAULT_CONTRACT_NAME.len())
+                    .map_or(false, |b| is_identifier_byte(*b)) =>
+            {
+                writer.write_all(&bytes[copied..i])?;
+                writer.write_all(name.as_bytes())?;
+                i += DEFAULT_CONTRACT_NAME.len();
+                copied = i;
+                continue;
+            }
+            None => {}
+        }
+        i += 1;
+    }
+    writer.write_all(&bytes[copied..])
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut bytes = name.bytes();
+    bytes
+        .next()
+        .map_or(false, |b| is_identifier_byte(b) && !b.is_ascii_digit())
+        && bytes.all(is_identifier_byte)
+}
+
+fn is_identifier_byte(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || byte == b'_' || byte == b'$'
+}
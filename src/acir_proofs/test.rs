@@ -1,21 +1,1877 @@
-use std::io::Read;
+use std::fs::File;
+use std::io::{Cursor, Read};
+use std::sync::Once;
+use std::thread;
+use std::time::{Duration, Instant};
 
 use base64::{engine::general_purpose, Engine};
 use flate2::read::GzDecoder;
 
-use super::acir_composer::get_circuit_sizes;
+use super::acir_composer::{
+    get_circuit_sizes, prove, prove_with_hint, verify, AcirComposer, KeyReadError,
+    ProofWriteError, VerifierWriteError, DEFAULT_MAX_SIZE_HINT,
+};
+use super::builder::{AcirComposerBuilder, Backend};
+use super::circuit::PreparedCircuit;
+use super::proof::{proof_public_input_count, Proof, ProofLengthError, ProofMode};
+use super::prover::AcirProver;
+use super::recursion::AGGREGATION_OBJECT_SIZE;
+use super::solidity::{self, SolidityOptions};
+use super::verification_key::{VerificationKey, VerificationKeyFields};
+use super::verifier::AcirVerifier;
+use crate::buffer::{read_vector, InputBuffer};
+use crate::error::BackendError;
+use crate::self_check::{CIRCUIT, WITNESS};
+use crate::srs::{netsrs::NetSrs, read_transcript, srs_init_once, test::write_transcript};
+use crate::validation;
 
-const BYTECODE: &str = "H4sIAAAAAAAA/7WTMRLEIAhFMYkp9ywgGrHbq6yz5v5H2JkdCyaxC9LgWDw+H9gBwMM91p7fPeOzIKdYjEeMLYdGTB8MpUrCmOohJJQkfYMwN4mSSy0ZC0VudKbCZ4cthqzVrsc/yw28dMZeWmrWerfBexnsxD6hJ7jUufr4GvyZFp8xpG0C14Pd8s/q29vPCBXypvmpDx7sD8opnfqIfsM1RNtxBQAA";
-const SOLVEDWITNESS: &str = "05000000000000000100000040000000000000003030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303302000000400000000000000030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303034030000004000000000000000333036343465373265313331613032396238353034356236383138313538356432383333653834383739623937303931343365316635393366303030303030300400000040000000000000003330363434653732653133316130323962383530343562363831383135383564323833336538343837396239373039313433653166353933663030303030303005000000400000000000000030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030";
-#[test]
-fn test_circuit_size_method() {
-    let acir_buffer = general_purpose::STANDARD.decode(BYTECODE).unwrap();
+/// Number of points every test initializes the CRS with.
+pub(crate) const TEST_SRS_POINTS: u32 = 2_u32.pow(10) + 1;
+
+/// Initializes the CRS once per test binary, going through a transcript file on disk.
+///
+/// Tests must not initialize the CRS any other way, as reinitializing it would change the CRS
+/// under tests that are proving.
+pub(crate) fn init_srs() {
+    static INIT: Once = Once::new();
+    INIT.call_once(|| {
+        let srs = NetSrs::new(TEST_SRS_POINTS);
+        let path = std::env::temp_dir()
+            .join(format!("barretenberg-sys-{}-transcript00.dat", std::process::id()));
+        write_transcript(&path, &srs.g1_data, &srs.g2_data);
+        let result = read_transcript(&path, srs.num_points);
+        let _ = std::fs::remove_file(&path);
+        let (points, g2_point) = result.unwrap();
+        srs_init_once(&points, srs.num_points, &g2_point).unwrap();
+    });
+}
+
+pub(crate) fn acir_buffer_uncompressed() -> Vec<u8> {
+    let acir_buffer = general_purpose::STANDARD.decode(CIRCUIT).unwrap();
     let mut decoder = GzDecoder::new(acir_buffer.as_slice());
     let mut acir_buffer_uncompressed = Vec::<u8>::new();
     decoder.read_to_end(&mut acir_buffer_uncompressed).unwrap();
+    acir_buffer_uncompressed
+}
+
+pub(crate) fn witness() -> Vec<u8> {
+    hex::decode(WITNESS).unwrap()
+}
+
+/// Serializes witness values the way [`WITNESS`] is: a count, then each witness index with
+/// its value as a length-prefixed hex string.
+fn encode_witness(values: &[[u8; 32]]) -> Vec<u8> {
+    let mut witness = (values.len() as u64).to_le_bytes().to_vec();
+    for (index, value) in (1u32..).zip(values) {
+        witness.extend_from_slice(&index.to_le_bytes());
+        witness.extend_from_slice(&64u64.to_le_bytes());
+        witness.extend_from_slice(hex::encode(value).as_bytes());
+    }
+    witness
+}
+
+/// Another solution of the fixture circuit, which asserts that `x != y` for a public `y`: the
+/// values are `x`, `y`, `x - y`, its inverse and `x == y`.
+fn other_witness() -> Vec<u8> {
+    let field = |hex_string: &str| -> [u8; 32] {
+        let bytes = hex::decode(format!("{hex_string:0>64}")).unwrap();
+        bytes.try_into().unwrap()
+    };
+    encode_witness(&[
+        field("5"),
+        field("3"),
+        field("2"),
+        field("183227397098d014dc2822db40c0ac2e9419f4243cdcb848a1f0fac9f8000001"),
+        field("0"),
+    ])
+}
 
-    let sizes = get_circuit_sizes(&acir_buffer_uncompressed);
+/// The fixture circuit with its private and public parameters replaced; [`witness`] solves it
+/// whichever of its five witnesses are public.
+fn circuit_with_parameters(private: &[u32], public: &[u32]) -> Vec<u8> {
+    let mut circuit = acir_buffer_uncompressed();
+    // The circuit ends with its private parameters {1}, public parameters {2} and no return
+    // values, each a bincode sequence of witness indices.
+    let tail = circuit.len() - 32;
+    assert_eq!(
+        hex::encode(&circuit[tail..]),
+        "0100000000000000010000000100000000000000020000000000000000000000"
+    );
+    circuit.truncate(tail);
+    for parameters in [private, public, &[]] {
+        circuit.extend_from_slice(&(parameters.len() as u64).to_le_bytes());
+        for index in parameters {
+            circuit.extend_from_slice(&index.to_le_bytes());
+        }
+    }
+    circuit
+}
+
+/// The fixture circuit with `y` private rather than public: a different circuit, with no public
+/// inputs, that [`witness`] still solves.
+fn private_circuit() -> Vec<u8> {
+    circuit_with_parameters(&[1, 2], &[])
+}
+
+/// The fixture circuit with a trailing byte, which passes validation but not deserialization.
+fn over_long_circuit() -> Vec<u8> {
+    let mut circuit = acir_buffer_uncompressed();
+    circuit.push(0);
+    circuit
+}
+
+#[test]
+fn test_circuit_size_method() {
+    let sizes = get_circuit_sizes(&acir_buffer_uncompressed()).unwrap();
     assert_eq!(sizes.exact, 5);
     assert_eq!(sizes.subgroup, 16);
     assert_eq!(sizes.total, 10);
 }
+
+#[test]
+fn test_circuit_sizes_are_consistent() {
+    let sizes = get_circuit_sizes(&acir_buffer_uncompressed()).unwrap();
+    assert!(sizes.exact <= sizes.total);
+    assert!(sizes.subgroup.is_power_of_two());
+    assert!(sizes.subgroup >= sizes.total);
+}
+
+#[test]
+fn test_circuit_size_method_rejects_truncated_circuit() {
+    let acir_buffer = acir_buffer_uncompressed();
+
+    let truncated = &acir_buffer[..acir_buffer.len() / 2];
+    match get_circuit_sizes(truncated) {
+        Err(err @ BackendError::Backend { .. }) => {
+            assert_eq!(err.op().name, "acir_get_circuit_sizes");
+            assert_eq!(err.op().inputs, vec![("cs", truncated.len())]);
+            let prefix = format!("acir_get_circuit_sizes failed (cs={} bytes): ", truncated.len());
+            assert!(err.to_string().starts_with(&prefix), "{}", err);
+        }
+        other => panic!("expected a backend error, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_init_proving_key_rejects_garbage() {
+    let acir_composer = AcirComposer::new(16).unwrap();
+    let garbage = [0xde, 0xad, 0xbe, 0xef, 0x01, 0x02, 0x03];
+
+    match acir_composer.init_proving_key(&garbage) {
+        Err(BackendError::MalformedInput { op, .. }) => {
+            assert_eq!(op.name, "acir_init_proving_key");
+            assert_eq!(acir_composer.last_backend_error(), None);
+        }
+        other => panic!("expected a malformed input error, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_init_proving_key_rejects_over_long_circuit() {
+    let acir_composer = AcirComposer::new(16).unwrap();
+
+    match acir_composer.init_proving_key(&over_long_circuit()) {
+        Err(BackendError::Backend { op, message, .. }) => {
+            assert_eq!(op.name, "acir_init_proving_key");
+            assert!(!message.is_empty());
+        }
+        other => panic!("expected a backend error, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_serialize_verification_key_into_fields() {
+    init_srs();
+    let acir_composer = AcirComposer::new(16).unwrap();
+    acir_composer
+        .init_proving_key(&acir_buffer_uncompressed())
+        .unwrap();
+    acir_composer.init_verification_key().unwrap();
+
+    let fields = acir_composer
+        .serialize_verification_key_into_fields()
+        .unwrap();
+    assert!(!fields.key_fields.is_empty());
+    assert!(fields.key_hash.iter().any(|byte| *byte != 0));
+    // The fields are those of the backend's buffer, after its element count.
+    let raw = acir_composer.verification_key_as_fields().unwrap().0;
+    assert_eq!(fields.key_fields.concat(), raw.as_bytes());
+
+    // The hash is written through the out pointer, so a second call must see the same one.
+    let again = acir_composer
+        .serialize_verification_key_into_fields()
+        .unwrap();
+    assert_eq!(again, fields);
+}
+
+#[test]
+fn test_verification_key_fields_display() {
+    let fields = VerificationKeyFields {
+        key_fields: vec![[0u8; 32], [0xab; 32]],
+        key_hash: [1u8; 32],
+    };
+    assert_eq!(
+        fields.key_fields_hex(),
+        [format!("0x{}", "00".repeat(32)), format!("0x{}", "ab".repeat(32))]
+    );
+    assert_eq!(fields.key_hash_hex(), format!("0x{}", "01".repeat(32)));
+    assert_eq!(
+        fields.to_string(),
+        format!(
+            "verification_key = [\"0x{}\", \"0x{}\"]\nkey_hash = \"0x{}\"",
+            "00".repeat(32),
+            "ab".repeat(32),
+            "01".repeat(32)
+        )
+    );
+}
+
+#[test]
+fn test_verification_key_hash() {
+    init_srs();
+    let acir_composer = AcirComposer::new(16).unwrap();
+    assert!(matches!(
+        acir_composer.verification_key_hash(),
+        Err(BackendError::VerificationKeyNotInitialized { .. })
+    ));
+    acir_composer
+        .init_proving_key(&acir_buffer_uncompressed())
+        .unwrap();
+    acir_composer.init_verification_key().unwrap();
+
+    let fields = acir_composer
+        .serialize_verification_key_into_fields()
+        .unwrap();
+    assert_eq!(acir_composer.verification_key_hash().unwrap(), fields.key_hash);
+}
+
+#[test]
+fn test_fields_as_field_elements() {
+    init_srs();
+    let acir_composer = AcirComposer::new(16).unwrap();
+    let proof = acir_composer
+        .create_proof(&acir_buffer_uncompressed(), &witness(), ProofMode::Recursive)
+        .unwrap();
+    let num_public_inputs = proof.num_public_inputs().unwrap();
+
+    let proof_bytes = acir_composer
+        .serialize_proof_into_fields(proof.as_ref(), num_public_inputs)
+        .unwrap();
+    let proof_fields = acir_composer
+        .proof_as_fields(proof.as_ref(), num_public_inputs)
+        .unwrap();
+    assert_eq!(proof_fields.as_bytes(), &proof_bytes[4..]);
+    let chunks = proof_fields.to_vec();
+    let count = u32::from_be_bytes(proof_bytes[..4].try_into().unwrap()) as usize;
+    assert_eq!((chunks.len(), proof_fields.len()), (count, count));
+    assert_eq!(chunks.concat(), &proof_bytes[4..]);
+
+    acir_composer.init_verification_key().unwrap();
+    let vkey = acir_composer
+        .serialize_verification_key_into_fields()
+        .unwrap();
+    let (vkey_fields, hash) = acir_composer.verification_key_as_fields().unwrap();
+    assert_eq!(vkey_fields.to_vec(), vkey.key_fields);
+    assert_eq!(hash, vkey.key_hash);
+}
+
+#[test]
+fn test_proof_mode_is_recorded_and_checked() {
+    init_srs();
+    let acir_composer = AcirComposer::new(16).unwrap();
+    let proof = acir_composer
+        .create_proof(&acir_buffer_uncompressed(), &witness(), ProofMode::Recursive)
+        .unwrap();
+    assert_eq!(proof.mode(), Some(ProofMode::Recursive));
+
+    // The recorded mode is the default, and contradicting it fails before the backend is called.
+    assert!(acir_composer.verify_proof(&proof, None).unwrap());
+    assert!(acir_composer
+        .verify_proof(&proof, ProofMode::Recursive)
+        .unwrap());
+    let err = acir_composer
+        .verify_proof(&proof, ProofMode::Standard)
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        BackendError::ProofModeMismatch {
+            proof: ProofMode::Recursive,
+            requested: ProofMode::Standard,
+            ..
+        }
+    ));
+    assert!(err
+        .to_string()
+        .starts_with("acir_verify_proof cannot verify a recursive proof as standard (proof=2176"));
+
+    // Bytes carry no mode, so an unlabelled proof is verified as standard unless told otherwise.
+    let bytes = Proof::from(proof.clone().into_bytes());
+    assert_eq!(bytes.mode(), None);
+    assert_eq!(bytes, proof);
+    assert!(!acir_composer.verify_proof(&bytes, None).unwrap());
+    assert!(acir_composer
+        .verify_proof(&bytes, ProofMode::Recursive)
+        .unwrap());
+    let labelled = bytes.with_mode(ProofMode::Recursive);
+    assert!(acir_composer.verify_proof(&labelled, None).unwrap());
+}
+
+#[test]
+#[allow(deprecated)]
+fn test_proof_mode_legacy_shims() {
+    init_srs();
+    let acir_composer = AcirComposer::new(16).unwrap();
+    let proof = acir_composer
+        .create_proof_legacy(&acir_buffer_uncompressed(), &witness(), false)
+        .unwrap();
+    assert_eq!(proof.mode(), Some(ProofMode::Standard));
+    assert!(acir_composer.verify_proof_legacy(&proof, false).unwrap());
+    assert!(matches!(
+        acir_composer.verify_proof_legacy(&proof, true),
+        Err(BackendError::ProofModeMismatch { .. })
+    ));
+}
+
+#[test]
+fn test_serialize_proof_into_fields_for_circuit() {
+    init_srs();
+    let acir_composer = AcirComposer::new(16).unwrap();
+    assert!(matches!(
+        acir_composer.serialize_proof_into_fields_for_circuit(&[0u8; 2176]),
+        Err(BackendError::ProvingKeyNotInitialized { .. })
+    ));
+    let proof = acir_composer
+        .create_proof(&acir_buffer_uncompressed(), &witness(), ProofMode::Recursive)
+        .unwrap();
+
+    let fields = acir_composer
+        .serialize_proof_into_fields_for_circuit(proof.as_ref())
+        .unwrap();
+    assert_eq!(
+        fields,
+        acir_composer
+            .serialize_proof_into_fields(proof.as_ref(), 1)
+            .unwrap()
+    );
+    assert_eq!(acir_composer.verification_key().unwrap().num_public_inputs(), Some(1));
+
+    // A count that disagrees with the proof's length is refused before the backend splits it.
+    let err = acir_composer
+        .serialize_proof_into_fields(proof.as_ref(), 2)
+        .unwrap_err();
+    assert!(matches!(err, BackendError::MalformedInput { .. }));
+    assert!(err.to_string().ends_with("the proof holds 1 public inputs, not 2"));
+
+    // A proof of another circuit, with one more public input, does not match this one.
+    let mut with_two = vec![0u8; 32];
+    with_two.extend_from_slice(proof.as_ref());
+    assert!(matches!(
+        acir_composer.serialize_proof_into_fields_for_circuit(&with_two),
+        Err(BackendError::MalformedInput { .. })
+    ));
+    assert!(acir_composer.serialize_proof_into_fields(&with_two, 2).is_ok());
+}
+
+#[test]
+fn test_get_recursive_artifacts() {
+    init_srs();
+    let acir_composer = AcirComposer::new(16).unwrap();
+    let proof = acir_composer
+        .create_proof(&acir_buffer_uncompressed(), &witness(), ProofMode::Recursive)
+        .unwrap();
+
+    let artifacts = acir_composer
+        .get_recursive_artifacts(proof.as_ref(), 1)
+        .unwrap();
+    assert_eq!(artifacts.proof_fields.len(), 1 + 93);
+    assert_eq!(artifacts.public_inputs(), proof.public_inputs(1));
+    assert_eq!(artifacts.proof_without_public_inputs().len(), 93);
+    assert_eq!(artifacts.vk_fields.len(), 6 + AGGREGATION_OBJECT_SIZE + 4 * 23);
+    assert_eq!(artifacts.vk_hash, acir_composer.verification_key_hash().unwrap());
+    let inputs = acir_composer.recursion_inputs(&proof).unwrap();
+    assert_eq!(
+        (inputs.proof, inputs.verification_key, inputs.key_hash),
+        (artifacts.proof_fields, artifacts.vk_fields, artifacts.vk_hash)
+    );
+
+    // The proof's length and the verification key must both agree with the count.
+    assert!(matches!(
+        acir_composer.get_recursive_artifacts(proof.as_ref(), 2),
+        Err(BackendError::MalformedInput { .. })
+    ));
+    let mut with_two = vec![0u8; 32];
+    with_two.extend_from_slice(proof.as_ref());
+    let err = acir_composer
+        .get_recursive_artifacts(&with_two, 2)
+        .unwrap_err();
+    assert!(matches!(err, BackendError::MalformedInput { .. }));
+    assert!(err.to_string().ends_with("the circuit of the verification key 1"));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_recursive_artifacts_serde() {
+    use super::recursion::RecursiveArtifacts;
+
+    let artifacts = RecursiveArtifacts {
+        proof_fields: vec![[1u8; 32], [2u8; 32], [3u8; 32]],
+        vk_fields: vec![[4u8; 32]],
+        vk_hash: [5u8; 32],
+        num_public_inputs: 1,
+    };
+    let json: serde_json::Value = serde_json::to_value(&artifacts).unwrap();
+    let field = |byte: &str| serde_json::Value::from(format!("0x{}", byte.repeat(32)));
+    assert_eq!(json["public_inputs"], serde_json::json!([field("01")]));
+    assert_eq!(json["proof"], serde_json::json!([field("02"), field("03")]));
+    assert_eq!(json["verification_key"], serde_json::json!([field("04")]));
+    assert_eq!(json["key_hash"], field("05"));
+    assert_eq!(serde_json::from_value::<RecursiveArtifacts>(json).unwrap(), artifacts);
+
+    // Noir writes field elements without leading zeros.
+    let short = r#"{"verification_key": ["0x4"], "proof": [], "public_inputs": ["0x0"],
+                    "key_hash": "0x00"}"#;
+    let parsed: RecursiveArtifacts = serde_json::from_str(short).unwrap();
+    let mut four = [0u8; 32];
+    four[31] = 4;
+    assert_eq!(parsed.vk_fields, [four]);
+    assert_eq!(parsed.public_inputs(), [[0u8; 32]]);
+    assert!(serde_json::from_str::<RecursiveArtifacts>(&short.replace("0x4", "4")).is_err());
+}
+
+#[test]
+fn test_recursion_inputs() {
+    init_srs();
+    let acir_composer = AcirComposer::new(16).unwrap();
+    let proof = acir_composer
+        .create_proof(&acir_buffer_uncompressed(), &witness(), ProofMode::Recursive)
+        .unwrap();
+    assert!(acir_composer.verify_proof(&proof, ProofMode::Recursive).unwrap());
+
+    let inputs = acir_composer.recursion_inputs(&proof).unwrap();
+    // One public input, then 93 fields of commitments and evaluations.
+    assert_eq!(inputs.proof.len(), 1 + 93);
+    assert_eq!(inputs.proof[0], proof.public_inputs(1)[0]);
+    assert_eq!(inputs.verification_key.len(), 6 + AGGREGATION_OBJECT_SIZE + 4 * 23);
+    // Circuit size and number of public inputs.
+    assert_eq!(inputs.verification_key[3][31], 16);
+    assert_eq!(inputs.verification_key[4][31], 1);
+    assert!(inputs.key_hash.iter().any(|byte| *byte != 0));
+}
+
+#[test]
+fn test_create_proof_on_another_thread() {
+    init_srs();
+    let acir_composer = AcirComposer::new(16).unwrap();
+
+    let handle = thread::spawn(move || {
+        let acir_buffer = acir_buffer_uncompressed();
+        let proof = acir_composer
+            .create_proof(&acir_buffer, &witness(), ProofMode::Standard)
+            .unwrap();
+        (acir_composer, proof)
+    });
+
+    let (acir_composer, proof) = handle.join().unwrap();
+    assert!(!proof.as_ref().is_empty());
+    assert!(acir_composer.verify_proof(&proof, ProofMode::Standard).unwrap());
+}
+
+#[test]
+fn test_composers_interleaved() {
+    init_srs();
+    let circuits = [acir_buffer_uncompressed(), private_circuit()];
+
+    // Both composers build and prove in turn on one thread, each keeping its own circuit.
+    let composers = [AcirComposer::new(16).unwrap(), AcirComposer::new(16).unwrap()];
+    let proofs: Vec<_> = composers
+        .iter()
+        .zip(&circuits)
+        .map(|(composer, circuit)| {
+            composer.create_proof(circuit, &witness(), ProofMode::Standard).unwrap()
+        })
+        .collect();
+    for (composer, proof) in composers.iter().zip(&proofs).rev() {
+        assert!(composer.verify_proof(proof, ProofMode::Standard).unwrap());
+    }
+
+    // And on two threads at once, which take turns inside the backend.
+    thread::scope(|scope| {
+        for circuit in &circuits {
+            scope.spawn(move || {
+                for _ in 0..4 {
+                    let prover = AcirComposer::new(16).unwrap();
+                    let proof = prover
+                        .create_proof(circuit, &witness(), ProofMode::Standard)
+                        .unwrap();
+                    let verifier = AcirVerifier::new(&prover.verification_key().unwrap()).unwrap();
+                    assert!(verifier.verify_proof(&proof, ProofMode::Standard).unwrap());
+                }
+            });
+        }
+    });
+}
+
+#[test]
+fn test_create_proof_into_writer() {
+    init_srs();
+    // Proofs are blinded, so proofs written elsewhere can only match `create_proof` in length.
+    let expected_len = AcirComposer::new(16)
+        .unwrap()
+        .create_proof(&acir_buffer_uncompressed(), &witness(), ProofMode::Standard)
+        .unwrap()
+        .as_ref()
+        .len();
+
+    let acir_composer = AcirComposer::new(16).unwrap();
+    let mut bytes = Vec::new();
+    let written = acir_composer
+        .create_proof_into(&acir_buffer_uncompressed(), &witness(), ProofMode::Standard, &mut bytes)
+        .unwrap();
+    assert_eq!((written, bytes.len()), (expected_len as u64, expected_len));
+    assert!(acir_composer.verify_proof(&Proof::from(bytes), ProofMode::Standard).unwrap());
+
+    let path = std::env::temp_dir().join(format!("barretenberg-sys-{}-proof", std::process::id()));
+    let acir_composer = AcirComposer::new(16).unwrap();
+    let written = acir_composer
+        .create_proof_into(
+            &acir_buffer_uncompressed(),
+            &witness(),
+            ProofMode::Standard,
+            File::create(&path).unwrap(),
+        )
+        .unwrap();
+    let file_bytes = std::fs::read(&path).unwrap();
+    let _ = std::fs::remove_file(&path);
+    assert_eq!((written, file_bytes.len()), (expected_len as u64, expected_len));
+    assert!(acir_composer.verify_proof(&Proof::from(file_bytes), ProofMode::Standard).unwrap());
+
+    let err = AcirComposer::new(16)
+        .unwrap()
+        .create_proof_into(&[0xff; 16], &witness(), ProofMode::Standard, &mut Vec::new())
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        ProofWriteError::Backend(BackendError::MalformedInput { .. })
+    ));
+}
+
+#[cfg(feature = "mmap")]
+#[test]
+fn test_create_proof_from_witness_file() {
+    use super::acir_composer::WitnessReadError;
+
+    init_srs();
+    let path =
+        std::env::temp_dir().join(format!("barretenberg-sys-{}-witness", std::process::id()));
+    std::fs::write(&path, witness()).unwrap();
+    let acir_composer = AcirComposer::new(16).unwrap();
+    let proof = acir_composer.create_proof_from_witness_file(
+        &acir_buffer_uncompressed(),
+        &path,
+        ProofMode::Standard,
+    );
+    let _ = std::fs::remove_file(&path);
+    let proof = proof.unwrap();
+
+    // Proofs are blinded, so the mapped witness can only be checked to prove the same statement.
+    let in_memory = AcirComposer::new(16)
+        .unwrap()
+        .create_proof(&acir_buffer_uncompressed(), &witness(), ProofMode::Standard)
+        .unwrap();
+    assert_eq!(proof.as_ref().len(), in_memory.as_ref().len());
+    assert_eq!(proof.split_public_inputs(1).0, in_memory.split_public_inputs(1).0);
+    assert!(acir_composer.verify_proof(&proof, ProofMode::Standard).unwrap());
+
+    let err = AcirComposer::new(16)
+        .unwrap()
+        .create_proof_from_witness_file(&acir_buffer_uncompressed(), &path, ProofMode::Standard)
+        .unwrap_err();
+    assert!(matches!(err, WitnessReadError::Io(_)));
+}
+
+#[cfg(feature = "tokio")]
+#[tokio::test]
+async fn test_create_proof_async() {
+    init_srs();
+    let acir_composer = AcirComposer::new(16).unwrap();
+
+    let (acir_composer, proof) = acir_composer
+        .create_proof_async(acir_buffer_uncompressed(), witness(), ProofMode::Standard)
+        .await;
+
+    assert!(acir_composer.verify_proof(&proof.unwrap(), ProofMode::Standard).unwrap());
+}
+
+#[test]
+fn test_create_proof_with_stats() {
+    init_srs();
+    let acir_composer = AcirComposer::new(16).unwrap();
+
+    let (proof, stats) = acir_composer
+        .create_proof_with_stats(&acir_buffer_uncompressed(), &witness(), ProofMode::Standard)
+        .unwrap();
+    assert_eq!(stats.proof_len, proof.as_ref().len());
+    assert_eq!(
+        (stats.circuit_sizes.exact, stats.circuit_sizes.total, stats.circuit_sizes.subgroup),
+        (5, 10, 16)
+    );
+    assert_eq!(stats.num_public_inputs, 1);
+    assert!(stats.duration > Duration::ZERO);
+}
+
+/// The ACIR fixtures are passed to the backend length-prefixed, the verification key as is; each
+/// only parses in the form its wrapper passes it in.
+#[test]
+fn test_input_layouts_match_fixtures() {
+    let witness = witness();
+    let prefixed = InputBuffer::new(&witness).serialize();
+    assert_eq!(prefixed[..4], (witness.len() as u32).to_be_bytes());
+    assert_eq!(read_vector(&prefixed), Ok(&witness[..]));
+    assert!(validation::witness(&witness).is_ok());
+    assert!(validation::witness(&prefixed).is_err());
+
+    let circuit = acir_buffer_uncompressed();
+    let prefixed = InputBuffer::new(&circuit).serialize();
+    assert_eq!(read_vector(&prefixed), Ok(&circuit[..]));
+    assert!(validation::constraint_system(&circuit).is_ok());
+
+    init_srs();
+    let acir_composer = AcirComposer::new(16).unwrap();
+    acir_composer.init_proving_key(&circuit).unwrap();
+    let verification_key = acir_composer.get_verification_key().unwrap();
+    let prefixed = InputBuffer::new(&verification_key).serialize();
+    assert!(validation::verification_key(&verification_key).is_ok());
+    assert!(validation::verification_key(&prefixed).is_err());
+}
+
+#[test]
+fn test_proof_public_input_count() {
+    init_srs();
+    let acir_composer = AcirComposer::new(16).unwrap();
+    let proof = acir_composer
+        .create_proof(&acir_buffer_uncompressed(), &witness(), ProofMode::Standard)
+        .unwrap();
+    assert_eq!(proof_public_input_count(proof.as_ref()), Ok(1));
+
+    // The same proof with two more public inputs prepended.
+    let mut with_three = vec![0u8; 64];
+    with_three.extend_from_slice(proof.as_ref());
+    assert_eq!(proof_public_input_count(&with_three), Ok(3));
+    let num_public_inputs = proof_public_input_count(&with_three).unwrap();
+    let fields = acir_composer
+        .proof_as_fields(&with_three, num_public_inputs)
+        .unwrap();
+    assert_eq!(fields.to_vec()[..3], [[0u8; 32], [0u8; 32], proof.public_inputs(1)[0]]);
+}
+
+#[test]
+fn test_proof_public_input_count_rejects_bad_lengths() {
+    assert_eq!(proof_public_input_count(&[0u8; 2144]), Ok(0));
+    assert_eq!(
+        proof_public_input_count(&[0u8; 2143]),
+        Err(ProofLengthError::TooShort { len: 2143 })
+    );
+    assert_eq!(
+        Proof::from(vec![0u8; 2144 + 31]).num_public_inputs(),
+        Err(ProofLengthError::PartialFieldElement {
+            public_inputs_len: 31
+        })
+    );
+}
+
+#[test]
+fn test_debug_reports_lifecycle() {
+    init_srs();
+    let acir_composer = AcirComposer::new(16).unwrap();
+    assert_eq!(
+        format!("{acir_composer:?}"),
+        "AcirComposer { size_hint: 16, pk_initialized: false, vk_initialized: false, \
+         circuit_sizes: None }"
+    );
+
+    acir_composer
+        .create_proof(&acir_buffer_uncompressed(), &witness(), ProofMode::Standard)
+        .unwrap();
+    assert_eq!(
+        format!("{acir_composer:?}"),
+        "AcirComposer { size_hint: 16, pk_initialized: true, vk_initialized: false, \
+         circuit_sizes: Some(CircuitSizes { exact: 5, total: 10, subgroup: 16 }) }"
+    );
+
+    acir_composer.init_verification_key().unwrap();
+    let vk = acir_composer.get_verification_key().unwrap();
+    assert!(format!("{acir_composer:?}").contains("pk_initialized: true, vk_initialized: true"));
+
+    let verifier = AcirComposer::new(16).unwrap();
+    verifier.load_verification_key(&vk).unwrap();
+    assert!(format!("{verifier:?}").contains("pk_initialized: false, vk_initialized: true"));
+}
+
+#[test]
+fn test_prove_witness_of_prepared_circuit() {
+    init_srs();
+    let circuit = PreparedCircuit::new(&acir_buffer_uncompressed()).unwrap();
+    assert_eq!(circuit.constraint_system(), acir_buffer_uncompressed());
+
+    for _ in 0..2 {
+        let mut acir_composer = AcirComposer::new(16).unwrap();
+        acir_composer.set_constraint_system(circuit.clone());
+        let proof = acir_composer.prove_witness(&witness(), ProofMode::Standard).unwrap();
+        assert!(acir_composer.verify_proof(&proof, ProofMode::Standard).unwrap());
+
+        let result = acir_composer.prove_witness(&witness(), ProofMode::Standard);
+        assert!(matches!(result, Err(BackendError::CircuitAlreadyBuilt { .. })));
+    }
+}
+
+#[test]
+fn test_with_constraint_system_proves_different_witnesses() {
+    init_srs();
+    let field = |value: u8| {
+        let mut field = [0u8; 32];
+        field[31] = value;
+        field
+    };
+    let mut minus_one = crate::field::MODULUS;
+    minus_one[31] -= 1;
+    assert_eq!(
+        encode_witness(&[field(3), field(4), minus_one, minus_one, field(0)]),
+        witness()
+    );
+
+    let circuit = PreparedCircuit::new(&acir_buffer_uncompressed()).unwrap();
+    let mut public_inputs = Vec::new();
+    for witness in [witness(), other_witness()] {
+        let acir_composer = AcirComposer::with_constraint_system(circuit.clone()).unwrap();
+        assert_eq!(acir_composer.size_hint(), 16);
+        let proof = acir_composer.prove_witness(&witness, ProofMode::Standard).unwrap();
+        assert!(acir_composer.verify_proof(&proof, ProofMode::Standard).unwrap());
+        public_inputs.push(proof.public_inputs(1)[0]);
+    }
+    assert_eq!(public_inputs, [field(4), field(3)]);
+}
+
+#[test]
+fn test_prove_witness_requires_constraint_system() {
+    let acir_composer = AcirComposer::new(16).unwrap();
+    let err = acir_composer.prove_witness(&witness(), ProofMode::Standard).unwrap_err();
+    assert!(matches!(err, BackendError::ConstraintSystemNotSet { .. }));
+    assert_eq!(
+        err.to_string(),
+        "acir_create_proof requires a constraint system set with set_constraint_system \
+         (size_hint=16)"
+    );
+
+    let result = PreparedCircuit::new(&[0u8; 4]);
+    assert!(matches!(result, Err(BackendError::MalformedInput { .. })));
+}
+
+#[test]
+fn test_prove_many() {
+    init_srs();
+    let acir_composer = AcirComposer::new(16).unwrap();
+    assert!(matches!(
+        acir_composer.prove_many([witness().as_slice()], ProofMode::Standard),
+        Err(BackendError::ConstraintSystemNotSet { .. })
+    ));
+
+    let circuit = PreparedCircuit::new(&acir_buffer_uncompressed()).unwrap();
+    let acir_composer = AcirComposer::with_constraint_system(circuit).unwrap();
+    let witnesses = [witness(), other_witness(), witness()];
+    let proofs = acir_composer
+        .prove_many(witnesses.iter().map(Vec::as_slice), ProofMode::Standard)
+        .unwrap();
+    assert_eq!(proofs.len(), 3);
+
+    // The composer itself has proven nothing, so it can still prove a witness.
+    acir_composer.prove_witness(&witness(), ProofMode::Standard).unwrap();
+    for proof in &proofs {
+        assert!(acir_composer.verify_proof(proof, ProofMode::Standard).unwrap());
+    }
+
+    let result = acir_composer.prove_many([witness().as_slice(), &[0xff; 4]], ProofMode::Standard);
+    assert!(matches!(result, Err(BackendError::MalformedInput { .. })));
+}
+
+#[test]
+#[ignore = "benchmark; proves 100 witnesses with create_proof and with prove_many"]
+fn test_prove_many_against_create_proof() {
+    const PROOFS: usize = 100;
+    init_srs();
+    let acir_buffer = acir_buffer_uncompressed();
+    let witness = witness();
+
+    let start = Instant::now();
+    for _ in 0..PROOFS {
+        let acir_composer = AcirComposer::new(16).unwrap();
+        acir_composer.create_proof(&acir_buffer, &witness, ProofMode::Standard).unwrap();
+    }
+    let create_proof = start.elapsed();
+
+    let start = Instant::now();
+    let acir_composer =
+        AcirComposer::with_constraint_system(PreparedCircuit::new(&acir_buffer).unwrap()).unwrap();
+    let proofs = acir_composer
+        .prove_many((0..PROOFS).map(|_| witness.as_slice()), ProofMode::Standard)
+        .unwrap();
+    let prove_many = start.elapsed();
+
+    assert_eq!(proofs.len(), PROOFS);
+    println!("{PROOFS} proofs: create_proof {create_proof:?}, prove_many {prove_many:?}");
+}
+
+#[test]
+#[ignore = "benchmark; proves 1000 witnesses with and without a prepared circuit"]
+fn test_prepared_circuit_avoids_copying_the_circuit() {
+    const PROOFS: usize = 1000;
+    init_srs();
+    let acir_buffer = acir_buffer_uncompressed();
+    let witness = witness();
+
+    let start = Instant::now();
+    for _ in 0..PROOFS {
+        let acir_composer = AcirComposer::new(16).unwrap();
+        acir_composer.create_proof(&acir_buffer, &witness, ProofMode::Standard).unwrap();
+    }
+    let copying = start.elapsed();
+
+    let start = Instant::now();
+    let circuit = PreparedCircuit::new(&acir_buffer).unwrap();
+    for _ in 0..PROOFS {
+        let mut acir_composer = AcirComposer::new(16).unwrap();
+        acir_composer.set_constraint_system(circuit.clone());
+        acir_composer.prove_witness(&witness, ProofMode::Standard).unwrap();
+    }
+    let prepared = start.elapsed();
+
+    // Every `create_proof` validates the circuit and serializes a copy of it; the prepared circuit
+    // is serialized once.
+    println!(
+        "{PROOFS} proofs: create_proof {copying:?} ({} circuit bytes copied), prove_witness \
+         {prepared:?} ({} bytes)",
+        PROOFS * (acir_buffer.len() + 4),
+        acir_buffer.len() + 4
+    );
+}
+
+#[test]
+fn test_create_proof_refuses_a_second_circuit() {
+    init_srs();
+    let acir_buffer = acir_buffer_uncompressed();
+
+    let acir_composer = AcirComposer::new(16).unwrap();
+    acir_composer.create_proof(&acir_buffer, &witness(), ProofMode::Standard).unwrap();
+    let result = acir_composer.create_proof(&acir_buffer, &witness(), ProofMode::Standard);
+    assert!(matches!(result, Err(BackendError::CircuitAlreadyBuilt { .. })), "{:?}", result);
+
+    let acir_composer = AcirComposer::new(16).unwrap();
+    acir_composer.init_proving_key(&acir_buffer).unwrap();
+    let err = acir_composer
+        .create_proof(&acir_buffer, &witness(), ProofMode::Standard)
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "acir_create_proof requires a composer that has not built a circuit yet \
+         (cs=1393 bytes, witness=388 bytes, size_hint=16)"
+    );
+}
+
+#[test]
+fn test_verification_key_requires_a_proving_key() {
+    let acir_composer = AcirComposer::new(16).unwrap();
+
+    let err = acir_composer.init_verification_key().unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "acir_init_verification_key requires an initialized proving key (size_hint=16)"
+    );
+    assert!(matches!(
+        acir_composer.get_verification_key(),
+        Err(BackendError::ProvingKeyNotInitialized { .. })
+    ));
+}
+
+#[test]
+fn test_verification_key_state_is_checked() {
+    let acir_composer = AcirComposer::new(16).unwrap();
+
+    let err = acir_composer
+        .verify_proof(&Proof::from(vec![0; 2176]), ProofMode::Standard)
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "acir_verify_proof requires an initialized verification key (proof=2176 bytes, \
+         size_hint=16)"
+    );
+    assert!(matches!(
+        acir_composer.get_solidity_verifier(),
+        Err(BackendError::VerificationKeyNotInitialized { .. })
+    ));
+    assert!(matches!(
+        acir_composer.serialize_verification_key_into_fields(),
+        Err(BackendError::VerificationKeyNotInitialized { .. })
+    ));
+}
+
+#[test]
+fn test_load_verification_key_from_reader() {
+    init_srs();
+    let prover = AcirComposer::new(16).unwrap();
+    let proof = prover
+        .create_proof(&acir_buffer_uncompressed(), &witness(), ProofMode::Standard)
+        .unwrap();
+    let vk = prover.get_verification_key().unwrap();
+
+    let verifier = AcirComposer::new(16).unwrap();
+    verifier
+        .load_verification_key_from_reader(Cursor::new(&vk))
+        .unwrap();
+    assert!(verifier.verify_proof(&proof, ProofMode::Standard).unwrap());
+
+    let path = std::env::temp_dir().join(format!("barretenberg-sys-{}-vk", std::process::id()));
+    std::fs::write(&path, &vk).unwrap();
+    let verifier = AcirComposer::new(16).unwrap();
+    let result = verifier.load_verification_key_from_reader(File::open(&path).unwrap());
+    let _ = std::fs::remove_file(&path);
+    result.unwrap();
+    assert!(verifier.verify_proof(&proof, ProofMode::Standard).unwrap());
+
+    let verifier = AcirComposer::new(16).unwrap();
+    match verifier.load_verification_key_from_reader(Cursor::new(&vk[..100])) {
+        Err(KeyReadError::Backend(BackendError::MalformedInput { .. })) => {}
+        other => panic!("expected a malformed input error, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_load_verification_key() {
+    init_srs();
+    let prover = AcirComposer::new(16).unwrap();
+    let proof = prover
+        .create_proof(&acir_buffer_uncompressed(), &witness(), ProofMode::Standard)
+        .unwrap();
+    let vk = prover.get_verification_key().unwrap();
+
+    let verifier = AcirComposer::new(16).unwrap();
+    for corrupt in [&vk[..vk.len() - 1], &[vk.as_slice(), &[0]].concat(), &[0xff; 16]] {
+        let result = verifier.load_verification_key(corrupt);
+        assert!(matches!(result, Err(BackendError::MalformedInput { .. })), "{:?}", result);
+    }
+    assert!(matches!(
+        verifier.verify_proof(&proof, ProofMode::Standard),
+        Err(BackendError::VerificationKeyNotInitialized { .. })
+    ));
+
+    verifier.load_verification_key(&vk).unwrap();
+    assert!(verifier.verify_proof(&proof, ProofMode::Standard).unwrap());
+}
+
+#[test]
+fn test_verify_many_proofs_with_one_verification_key() {
+    init_srs();
+    let prover = AcirComposer::new(16).unwrap();
+    let mut proofs = vec![prover
+        .create_proof(&acir_buffer_uncompressed(), &witness(), ProofMode::Standard)
+        .unwrap()];
+    let vk = prover.verification_key().unwrap();
+    assert_eq!(vk.as_ref(), prover.get_verification_key().unwrap());
+    for witness in [witness(), other_witness()] {
+        let prover = AcirComposer::new(16).unwrap();
+        proofs.push(
+            prover
+                .create_proof(&acir_buffer_uncompressed(), &witness, ProofMode::Standard)
+                .unwrap(),
+        );
+        assert_eq!(prover.verification_key().unwrap(), vk);
+    }
+
+    let verifier = AcirComposer::with_verification_key(&vk).unwrap();
+    for proof in &proofs {
+        assert!(verifier.verify_proof(proof, ProofMode::Standard).unwrap());
+    }
+    let mut tampered = proofs[0].clone().into_bytes();
+    tampered[31] ^= 0x01;
+    assert!(!verifier.verify_proof(&Proof::from(tampered), ProofMode::Standard).unwrap());
+
+    let truncated = VerificationKey::from(vk.as_ref()[..100].to_vec());
+    assert!(matches!(
+        AcirComposer::with_verification_key(&truncated),
+        Err(BackendError::MalformedInput { .. })
+    ));
+}
+
+#[test]
+fn test_close_frees_the_composer_once() {
+    init_srs();
+    let acir_composer = AcirComposer::new(16).unwrap();
+    acir_composer
+        .create_proof(&acir_buffer_uncompressed(), &witness(), ProofMode::Standard)
+        .unwrap();
+    acir_composer.close().unwrap();
+
+    // Deleting again, and then dropping, must not free the C++ composer a second time.
+    let mut acir_composer = AcirComposer::new(16).unwrap();
+    acir_composer.delete().unwrap();
+    acir_composer.delete().unwrap();
+    drop(acir_composer);
+
+    // A constructor that fails after creating the C++ composer drops it on the way out.
+    let truncated = VerificationKey::from(vec![0; 100]);
+    assert!(AcirComposer::with_verification_key(&truncated).is_err());
+    assert!(matches!(
+        AcirProver::new(16).unwrap().into_verifier(),
+        Err(BackendError::ProvingKeyNotInitialized { .. })
+    ));
+    AcirProver::new(16).unwrap().close().unwrap();
+}
+
+#[test]
+fn test_reset_proves_another_circuit() {
+    init_srs();
+    let mut acir_composer = AcirComposer::new(16).unwrap();
+    let proof_a = acir_composer
+        .create_proof(&acir_buffer_uncompressed(), &witness(), ProofMode::Standard)
+        .unwrap();
+    let vk_a = acir_composer.verification_key().unwrap();
+
+    acir_composer.reset().unwrap();
+    assert_eq!(acir_composer.size_hint(), 16);
+    assert!(!acir_composer.has_verification_key());
+    let proof_b = acir_composer
+        .create_proof(&private_circuit(), &witness(), ProofMode::Standard)
+        .unwrap();
+    let vk_b = acir_composer.verification_key().unwrap();
+    assert_ne!(vk_a, vk_b);
+
+    let verifier_a = AcirComposer::with_verification_key(&vk_a).unwrap();
+    assert!(verifier_a.verify_proof(&proof_a, ProofMode::Standard).unwrap());
+    assert!(acir_composer.verify_proof(&proof_b, ProofMode::Standard).unwrap());
+    assert!(!matches!(
+        verifier_a.verify_proof(&proof_b, ProofMode::Standard),
+        Ok(true)
+    ));
+    assert!(!matches!(
+        acir_composer.verify_proof(&proof_a, ProofMode::Standard),
+        Ok(true)
+    ));
+}
+
+#[test]
+fn test_num_public_inputs() {
+    init_srs();
+    let circuits = [
+        (private_circuit(), 0),
+        (acir_buffer_uncompressed(), 1),
+        (circuit_with_parameters(&[], &[1, 2, 3, 4, 5]), 5),
+    ];
+    for (circuit, expected) in circuits {
+        let prover = AcirComposer::new(16).unwrap();
+        assert!(matches!(
+            prover.num_public_inputs(),
+            Err(BackendError::ProvingKeyNotInitialized { .. })
+        ));
+        let proof = prover.create_proof(&circuit, &witness(), ProofMode::Standard).unwrap();
+        assert_eq!(prover.num_public_inputs().unwrap(), expected);
+        assert_eq!(proof.num_public_inputs(), Ok(expected));
+
+        let verifier = AcirVerifier::new(&prover.verification_key().unwrap()).unwrap();
+        assert_eq!(verifier.num_public_inputs().unwrap(), expected);
+        assert!(verifier.verify_proof(&proof, ProofMode::Standard).unwrap());
+    }
+}
+
+#[test]
+fn test_circuit_size_after_init_proving_key() {
+    init_srs();
+    for circuit in [acir_buffer_uncompressed(), circuit_with_parameters(&[], &[1, 2, 3, 4, 5])] {
+        let estimate = get_circuit_sizes(&circuit).unwrap();
+        let prover = AcirProver::new(estimate.subgroup).unwrap();
+        assert!(matches!(
+            prover.circuit_size(),
+            Err(BackendError::ProvingKeyNotInitialized { .. })
+        ));
+        prover.init_proving_key(&circuit).unwrap();
+        let size = prover.circuit_size().unwrap();
+        assert!(size >= estimate.subgroup, "{size} < {}", estimate.subgroup);
+        assert!(size.is_power_of_two());
+
+        let verifier = prover.into_verifier().unwrap();
+        assert_eq!(verifier.circuit_size().unwrap(), size);
+    }
+}
+
+#[test]
+fn test_verification_key_is_cached() {
+    init_srs();
+    let acir_composer = AcirComposer::new(16).unwrap();
+    acir_composer
+        .create_proof(&acir_buffer_uncompressed(), &witness(), ProofMode::Standard)
+        .unwrap();
+    let vk = acir_composer.verification_key().unwrap();
+    let again = acir_composer.verification_key().unwrap();
+    assert_eq!(again.as_ref().as_ptr(), vk.as_ref().as_ptr());
+
+    acir_composer.init_verification_key().unwrap();
+    let recomputed = acir_composer.verification_key().unwrap();
+    assert_ne!(recomputed.as_ref().as_ptr(), vk.as_ref().as_ptr());
+    assert_eq!(recomputed, vk);
+
+    // Loading a key drops the cached one, even when it is the same key.
+    acir_composer.load_verification_key(vk.as_ref()).unwrap();
+    let loaded = acir_composer.verification_key().unwrap();
+    assert_ne!(loaded.as_ref().as_ptr(), recomputed.as_ref().as_ptr());
+    assert_eq!(loaded, vk);
+    assert_eq!(
+        acir_composer.verification_key().unwrap().as_ref().as_ptr(),
+        loaded.as_ref().as_ptr()
+    );
+}
+
+#[test]
+fn test_prover_into_verifier() {
+    init_srs();
+    let prover = AcirProver::new(16).unwrap();
+    let proof = prover
+        .create_proof(&acir_buffer_uncompressed(), &witness(), ProofMode::Standard)
+        .unwrap();
+    let vk = prover.verification_key().unwrap();
+    let verifier = prover.into_verifier().unwrap();
+    assert!(verifier.verify_proof(&proof, ProofMode::Standard).unwrap());
+    assert_eq!(verifier.verification_key().unwrap(), vk);
+
+    assert!(matches!(
+        AcirProver::new(16).unwrap().into_verifier(),
+        Err(BackendError::ProvingKeyNotInitialized { .. })
+    ));
+}
+
+#[test]
+fn test_verifier_from_verification_key_bytes() {
+    init_srs();
+    let prover = AcirProver::new(16).unwrap();
+    let proof = prover
+        .create_proof(&acir_buffer_uncompressed(), &witness(), ProofMode::Standard)
+        .unwrap();
+    let bytes = prover.verification_key().unwrap().into_bytes();
+
+    let verifier = AcirVerifier::new(&VerificationKey::from(bytes)).unwrap();
+    assert!(verifier.verify_proof(&proof, ProofMode::Standard).unwrap());
+    assert_eq!(verifier.verify_proofs(&[proof], ProofMode::Standard), [Ok(true)]);
+    assert!(verifier
+        .get_solidity_verifier()
+        .unwrap()
+        .contains("UltraVerificationKey"));
+}
+
+#[test]
+fn test_get_verification_key_buffer() {
+    init_srs();
+    let prover = AcirComposer::new(16).unwrap();
+    let proof = prover
+        .create_proof(&acir_buffer_uncompressed(), &witness(), ProofMode::Standard)
+        .unwrap();
+    let vk = prover.get_verification_key_buffer().unwrap();
+    assert_eq!(&vk[..], prover.get_verification_key().unwrap());
+
+    let verifier = AcirComposer::new(16).unwrap();
+    verifier.load_verification_key(&vk[..]).unwrap();
+    assert!(verifier.verify_proof(&proof, ProofMode::Standard).unwrap());
+}
+
+#[test]
+fn test_verify_proof_rejects_tampered_proof() {
+    init_srs();
+    let acir_buffer = acir_buffer_uncompressed();
+    let acir_composer = AcirComposer::new(16).unwrap();
+    let proof = acir_composer
+        .create_proof(&acir_buffer, &witness(), ProofMode::Standard)
+        .unwrap();
+    acir_composer.init_verification_key().unwrap();
+
+    // Change the public input rather than a group element, which the backend rejects outright.
+    let mut bytes = proof.into_bytes();
+    bytes[31] ^= 0x01;
+    assert!(!acir_composer.verify_proof(&Proof::from(bytes), ProofMode::Standard).unwrap());
+}
+
+#[test]
+fn test_create_proof_split() {
+    init_srs();
+    let acir_composer = AcirComposer::new(16).unwrap();
+    let (public_inputs, proof_without_inputs) = acir_composer
+        .create_proof_split(&acir_buffer_uncompressed(), &witness(), ProofMode::Standard)
+        .unwrap();
+    let mut y = [0u8; 32];
+    y[31] = 4;
+    assert_eq!(public_inputs, [y]);
+    assert_eq!(proof_without_inputs.len(), 2144);
+
+    let proof = Proof::from_parts(&public_inputs, &proof_without_inputs);
+    assert!(acir_composer.verify_proof(&proof, ProofMode::Standard).unwrap());
+    assert_eq!(proof.split_public_inputs(1), (public_inputs, proof_without_inputs));
+
+    // A count larger than the proof holds stops at its last whole element.
+    let short = Proof::from(vec![7u8; 70]);
+    assert_eq!(short.split_public_inputs(5), (vec![[7u8; 32]; 2], vec![7u8; 6]));
+}
+
+#[test]
+fn test_verify_proofs() {
+    init_srs();
+    let acir_composer = AcirComposer::new(16).unwrap();
+    let proof = acir_composer
+        .create_proof(&acir_buffer_uncompressed(), &witness(), ProofMode::Standard)
+        .unwrap();
+    let mut tampered = proof.as_ref().to_vec();
+    tampered[31] ^= 0x01;
+    let truncated = proof.as_ref()[..64].to_vec();
+    let proofs = [
+        proof.clone(),
+        Proof::from(tampered),
+        Proof::from(truncated),
+        proof,
+    ];
+    assert!(matches!(
+        AcirComposer::new(16).unwrap().verify_proofs(&proofs[..1], ProofMode::Standard)[..],
+        [Err(BackendError::VerificationKeyNotInitialized { .. })]
+    ));
+
+    let results = acir_composer.verify_proofs(&proofs, ProofMode::Standard);
+    assert_eq!(results.len(), 4);
+    assert_eq!(results[0], Ok(true));
+    assert_eq!(results[1], Ok(false));
+    assert!(matches!(results[2], Err(BackendError::Backend { .. })));
+    assert_eq!(results[3], Ok(true));
+    assert!(acir_composer.verify_proofs(&[], ProofMode::Standard).is_empty());
+
+    // The mode each proof recorded is checked, or used when none is given.
+    assert!(matches!(
+        acir_composer.verify_proofs(&proofs[..1], ProofMode::Recursive)[..],
+        [Err(BackendError::ProofModeMismatch { .. })]
+    ));
+    assert_eq!(acir_composer.verify_proofs(&proofs[..1], None), [Ok(true)]);
+}
+
+#[test]
+fn test_standalone_verify() {
+    init_srs();
+    let prover = AcirComposer::new(16).unwrap();
+    let proof = prover
+        .create_proof(&acir_buffer_uncompressed(), &witness(), ProofMode::Standard)
+        .unwrap();
+    let vk = prover.get_verification_key().unwrap();
+    let mut tampered = proof.as_ref().to_vec();
+    tampered[31] ^= 0x01;
+
+    let verifier = AcirComposer::new(16).unwrap();
+    verifier.load_verification_key(&vk).unwrap();
+    for bytes in [proof.as_ref(), &tampered] {
+        assert_eq!(
+            verify(&vk, bytes, ProofMode::Standard),
+            verifier.verify_proof(&Proof::from(bytes.to_vec()), ProofMode::Standard)
+        );
+    }
+    assert_eq!(verify(&vk, proof.as_ref(), ProofMode::Standard), Ok(true));
+    assert!(matches!(
+        verify(&vk[..100], proof.as_ref(), ProofMode::Standard),
+        Err(BackendError::MalformedInput { .. })
+    ));
+
+    thread::scope(|scope| {
+        let handles: Vec<_> = (0..8)
+            .map(|i| {
+                let (vk, proof, tampered) = (&vk, proof.as_ref(), &tampered);
+                scope.spawn(move || {
+                    let bytes = if i % 2 == 0 { proof } else { tampered.as_slice() };
+                    (i, verify(vk, bytes, ProofMode::Standard))
+                })
+            })
+            .collect();
+        for handle in handles {
+            let (i, result) = handle.join().unwrap();
+            assert_eq!(result, Ok(i % 2 == 0));
+        }
+    });
+}
+
+#[test]
+fn test_one_shot_prove() {
+    init_srs();
+    let artifacts = prove(&acir_buffer_uncompressed(), &witness(), ProofMode::Standard).unwrap();
+    assert_eq!(artifacts.proof.num_public_inputs(), Ok(1));
+    let vk = &artifacts.verification_key;
+    assert_eq!(verify(vk, artifacts.proof.as_ref(), ProofMode::Standard), Ok(true));
+
+    let artifacts =
+        prove_with_hint(&acir_buffer_uncompressed(), &witness(), ProofMode::Standard, 16).unwrap();
+    let vk = &artifacts.verification_key;
+    assert_eq!(verify(vk, artifacts.proof.as_ref(), ProofMode::Standard), Ok(true));
+
+    assert!(matches!(
+        prove(&[0xff; 16], &witness(), ProofMode::Standard),
+        Err(BackendError::MalformedInput { .. })
+    ));
+    assert!(matches!(
+        prove_with_hint(&acir_buffer_uncompressed(), &witness(), ProofMode::Standard, 0),
+        Err(BackendError::InvalidSizeHint { .. })
+    ));
+}
+
+#[test]
+fn test_verify_proof_errors_on_truncated_proof() {
+    init_srs();
+    let acir_buffer = acir_buffer_uncompressed();
+    let acir_composer = AcirComposer::new(16).unwrap();
+    let proof = acir_composer
+        .create_proof(&acir_buffer, &witness(), ProofMode::Standard)
+        .unwrap();
+    acir_composer.init_verification_key().unwrap();
+
+    let truncated = Proof::from(proof.as_ref()[..64].to_vec());
+    let result = acir_composer.verify_proof(&truncated, ProofMode::Standard);
+    assert!(matches!(result, Err(BackendError::Backend { .. })));
+}
+
+#[test]
+fn test_verify_proof_errors_on_garbage_proof() {
+    init_srs();
+    let acir_composer = AcirComposer::new(16).unwrap();
+    let proof = acir_composer
+        .create_proof(&acir_buffer_uncompressed(), &witness(), ProofMode::Standard)
+        .unwrap();
+    acir_composer.init_verification_key().unwrap();
+    assert!(acir_composer.verify_proof(&proof, ProofMode::Standard).unwrap());
+
+    // Right length, but the group elements are not on the curve.
+    let garbage = Proof::from(vec![0xde; proof.as_ref().len()]);
+    let result = acir_composer.verify_proof(&garbage, ProofMode::Standard);
+    assert!(matches!(result, Err(BackendError::Backend { .. })));
+}
+
+#[test]
+fn test_backend_error_display_includes_context() {
+    let acir_composer = AcirComposer::new(16).unwrap();
+
+    let err = acir_composer.init_proving_key(&over_long_circuit()).unwrap_err();
+    let message = match &err {
+        BackendError::Backend { message, .. } => message.clone(),
+        other => panic!("expected a backend error, got {:?}", other),
+    };
+    assert_eq!(
+        err.to_string(),
+        format!("acir_init_proving_key failed (cs=1394 bytes, size_hint=16): {message}")
+    );
+}
+
+#[test]
+fn test_last_backend_error_is_cleared_by_success() {
+    init_srs();
+    let acir_composer = AcirComposer::new(16).unwrap();
+    assert_eq!(acir_composer.last_backend_error(), None);
+
+    let err = acir_composer.init_proving_key(&over_long_circuit()).unwrap_err();
+    match err {
+        BackendError::Backend { message, .. } => {
+            assert_eq!(acir_composer.last_backend_error(), Some(message))
+        }
+        other => panic!("expected a backend error, got {:?}", other),
+    }
+
+    acir_composer
+        .init_proving_key(&acir_buffer_uncompressed())
+        .unwrap();
+    assert_eq!(acir_composer.last_backend_error(), None);
+}
+
+#[test]
+fn test_create_proof_rejects_garbage_circuit() {
+    let acir_composer = AcirComposer::new(16).unwrap();
+    let garbage = [0xde, 0xad, 0xbe, 0xef];
+
+    match acir_composer.create_proof(&garbage, &witness(), ProofMode::Standard) {
+        Err(BackendError::MalformedInput { op, .. }) => {
+            assert_eq!(op.name, "acir_create_proof");
+            assert_eq!(op.inputs, vec![("cs", 4), ("witness", witness().len())]);
+        }
+        other => panic!("expected a malformed input error, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_create_proof_rejects_malformed_witness() {
+    let acir_composer = AcirComposer::new(16).unwrap();
+    let circuit = acir_buffer_uncompressed();
+    let witness = witness();
+    let over_long = [witness.as_slice(), &[0]].concat();
+
+    for malformed in [&[][..], &witness[..witness.len() - 1], &over_long] {
+        let result = acir_composer.create_proof(&circuit, malformed, ProofMode::Standard);
+        assert!(matches!(result, Err(BackendError::MalformedInput { .. })), "{:?}", result);
+    }
+}
+
+#[test]
+fn test_size_hint_is_rounded_up_to_a_power_of_two() {
+    assert_eq!(AcirComposer::new(1).unwrap().size_hint(), 1);
+    assert_eq!(AcirComposer::new(16).unwrap().size_hint(), 16);
+    assert_eq!(AcirComposer::new(17).unwrap().size_hint(), 32);
+    assert_eq!(
+        AcirComposer::new(DEFAULT_MAX_SIZE_HINT).unwrap().size_hint(),
+        DEFAULT_MAX_SIZE_HINT
+    );
+}
+
+#[test]
+fn test_subgroup_size_hint_is_not_rounded_again() {
+    let sizes = get_circuit_sizes(&acir_buffer_uncompressed()).unwrap();
+    let acir_composer = AcirComposer::new(sizes.subgroup).unwrap();
+    assert_eq!(acir_composer.size_hint(), sizes.subgroup);
+}
+
+#[test]
+fn test_unreasonable_size_hints_are_rejected() {
+    for size_hint in [0, DEFAULT_MAX_SIZE_HINT + 1, u32::MAX] {
+        match AcirComposer::new(size_hint) {
+            Err(BackendError::InvalidSizeHint { got, max, .. }) => {
+                assert_eq!((got, max), (size_hint, DEFAULT_MAX_SIZE_HINT))
+            }
+            other => panic!("expected an invalid size hint, got {:?}", other.err()),
+        }
+    }
+}
+
+#[test]
+fn test_custom_max_size_hint() {
+    assert_eq!(AcirComposer::with_max_size_hint(64, 64).unwrap().size_hint(), 64);
+
+    let err = AcirComposer::with_max_size_hint(33, 63).err().unwrap();
+    assert_eq!(
+        err.to_string(),
+        "acir_new_acir_composer rejected size hint 33: it must be between 1 and 63 once rounded \
+         up to a power of two"
+    );
+}
+
+#[test]
+fn test_builder_ultra_plonk_round_trips_a_proof() {
+    init_srs();
+    let acir_composer = AcirComposerBuilder::new()
+        .size_hint(16)
+        .backend(Backend::UltraPlonk)
+        .build()
+        .unwrap();
+
+    let proof = acir_composer
+        .create_proof(&acir_buffer_uncompressed(), &witness(), ProofMode::Standard)
+        .unwrap();
+    assert!(acir_composer.verify_proof(&proof, ProofMode::Standard).unwrap());
+}
+
+#[test]
+fn test_builder_from_circuit() {
+    init_srs();
+    let acir_composer = AcirComposerBuilder::from_circuit(&acir_buffer_uncompressed())
+        .unwrap()
+        .build()
+        .unwrap();
+    assert_eq!(acir_composer.size_hint(), 16);
+
+    let proof = acir_composer
+        .create_proof(&acir_buffer_uncompressed(), &witness(), ProofMode::Standard)
+        .unwrap();
+    assert!(acir_composer.verify_proof(&proof, ProofMode::Standard).unwrap());
+
+    assert!(matches!(
+        AcirComposerBuilder::from_circuit(&[0xff; 16]),
+        Err(BackendError::MalformedInput { .. })
+    ));
+}
+
+#[test]
+fn test_builder_defaults_match_new() {
+    assert_eq!(Backend::default(), Backend::UltraPlonk);
+    let acir_composer = AcirComposerBuilder::default().size_hint(17).build().unwrap();
+    assert_eq!(acir_composer.size_hint(), AcirComposer::new(17).unwrap().size_hint());
+}
+
+#[test]
+fn test_builder_validates_size_hint() {
+    match AcirComposerBuilder::new().build() {
+        Err(BackendError::InvalidSizeHint { got: 0, .. }) => {}
+        other => panic!("expected an invalid size hint, got {:?}", other.err()),
+    }
+    match AcirComposerBuilder::new().size_hint(33).max_size_hint(63).build() {
+        Err(BackendError::InvalidSizeHint { got: 33, max: 63, .. }) => {}
+        other => panic!("expected an invalid size hint, got {:?}", other.err()),
+    }
+}
+
+#[test]
+fn test_get_solidity_verifier() {
+    init_srs();
+    let acir_composer = AcirComposer::new(16).unwrap();
+    acir_composer
+        .init_proving_key(&acir_buffer_uncompressed())
+        .unwrap();
+    acir_composer.init_verification_key().unwrap();
+
+    let verifier = acir_composer.get_solidity_verifier().unwrap();
+    assert!(verifier.contains("UltraVerificationKey"));
+}
+
+/// Whether every brace in `source` is closed, in order, and none is left open.
+fn braces_balanced(source: &str) -> bool {
+    let mut depth = 0usize;
+    for c in source.chars() {
+        match c {
+            '{' => depth += 1,
+            '}' => match depth.checked_sub(1) {
+                Some(d) => depth = d,
+                None => return false,
+            },
+            _ => {}
+        }
+    }
+    depth == 0
+}
+
+#[test]
+fn test_write_solidity_verifier() {
+    init_srs();
+    let acir_composer = AcirComposer::new(16).unwrap();
+    let options = SolidityOptions {
+        contract_name: "MyVerificationKey".to_owned(),
+        solidity_pragma: "^0.8.19".to_owned(),
+        spdx_license: Some("MIT".to_owned()),
+    };
+    assert!(matches!(
+        acir_composer.write_solidity_verifier(Vec::new(), &options),
+        Err(VerifierWriteError::Backend(BackendError::VerificationKeyNotInitialized { .. }))
+    ));
+    acir_composer
+        .init_proving_key(&acir_buffer_uncompressed())
+        .unwrap();
+    acir_composer.init_verification_key().unwrap();
+    let generated = acir_composer.get_solidity_verifier().unwrap();
+
+    let mut source = Vec::new();
+    acir_composer
+        .write_solidity_verifier(&mut source, &SolidityOptions::default())
+        .unwrap();
+    assert_eq!(String::from_utf8(source).unwrap(), generated);
+
+    let mut source = Vec::new();
+    acir_composer
+        .write_solidity_verifier(&mut source, &options)
+        .unwrap();
+    let source = String::from_utf8(source).unwrap();
+    assert!(braces_balanced(&source));
+    assert!(source.contains("\n// SPDX-License-Identifier: MIT\n"));
+    assert!(source.contains("\npragma solidity ^0.8.19;\n"));
+    assert!(source.contains("\nlibrary MyVerificationKey {\n"));
+    assert!(!source.contains("UltraVerificationKey"));
+    assert_eq!(source.lines().count(), generated.lines().count());
+
+    let mut source = Vec::new();
+    let options = SolidityOptions {
+        spdx_license: None,
+        ..options
+    };
+    acir_composer
+        .write_solidity_verifier(&mut source, &options)
+        .unwrap();
+    let source = String::from_utf8(source).unwrap();
+    assert!(!source.contains("SPDX"));
+    assert_eq!(source.lines().count() + 1, generated.lines().count());
+
+    for options in [
+        SolidityOptions {
+            contract_name: "1Verifier".to_owned(),
+            ..SolidityOptions::default()
+        },
+        SolidityOptions {
+            contract_name: "My Verifier".to_owned(),
+            ..SolidityOptions::default()
+        },
+        SolidityOptions {
+            solidity_pragma: "^0.8.19; contract X {}".to_owned(),
+            ..SolidityOptions::default()
+        },
+        SolidityOptions {
+            spdx_license: Some("MIT\ncontract X {}".to_owned()),
+            ..SolidityOptions::default()
+        },
+    ] {
+        assert!(matches!(
+            acir_composer.write_solidity_verifier(Vec::new(), &options),
+            Err(VerifierWriteError::Backend(BackendError::MalformedInput { .. }))
+        ));
+    }
+}
+
+#[test]
+fn test_solidity_rename_skips_strings_and_comments() {
+    let source = "library UltraVerificationKey {} // UltraVerificationKey\n\
+                  string s = \"UltraVerificationKey\"; UltraVerificationKeys x;\n\
+                  UltraVerificationKey.load();";
+    let options = SolidityOptions {
+        contract_name: "Key".to_owned(),
+        ..SolidityOptions::default()
+    };
+    let mut renamed = Vec::new();
+    solidity::write_source(source, &options, &mut renamed).unwrap();
+    assert_eq!(
+        String::from_utf8(renamed).unwrap(),
+        "library Key {} // UltraVerificationKey\n\
+         string s = \"UltraVerificationKey\"; UltraVerificationKeys x;\n\
+         Key.load();"
+    );
+}
+
+#[test]
+fn test_get_verification_key_solidity_bytes() {
+    init_srs();
+    let acir_composer = AcirComposer::new(16).unwrap();
+    assert!(matches!(
+        acir_composer.get_verification_key_solidity_bytes(),
+        Err(BackendError::VerificationKeyNotInitialized { .. })
+    ));
+    acir_composer
+        .init_proving_key(&acir_buffer_uncompressed())
+        .unwrap();
+    acir_composer.init_verification_key().unwrap();
+
+    let key = acir_composer.get_verification_key_solidity_bytes().unwrap();
+    // Circuit size, public input count, root of unity and domain inverse; 23 commitments; the
+    // recursion flag and public input indices; and the G2 point.
+    assert_eq!(key.len(), 32 * (4 + 2 * 23 + 2 + 4));
+    let words: Vec<&[u8]> = key.chunks_exact(32).collect();
+    assert_eq!(hex::encode(words[0]), format!("{:064x}", 16));
+    assert_eq!(hex::encode(words[1]), format!("{:064x}", 1));
+    let source = acir_composer.get_solidity_verifier().unwrap();
+    for word in words.iter().filter(|word| word.iter().any(|byte| *byte != 0)) {
+        assert!(source.contains(&hex::encode(word)));
+    }
+}
+
+/// Resident set size of the current process in pages, read from `/proc/self/statm`.
+fn resident_pages() -> usize {
+    let statm = std::fs::read_to_string("/proc/self/statm").unwrap();
+    statm.split_whitespace().nth(1).unwrap().parse().unwrap()
+}
+
+#[test]
+#[ignore = "slow; checks that repeated backend failures do not grow memory"]
+fn test_repeated_failures_do_not_leak() {
+    let acir_composer = AcirComposer::new(16).unwrap();
+    let garbage = over_long_circuit();
+
+    for _ in 0..1_000 {
+        assert!(acir_composer.init_proving_key(&garbage).is_err());
+    }
+    let baseline = resident_pages();
+    for _ in 0..10_000 {
+        assert!(acir_composer.init_proving_key(&garbage).is_err());
+    }
+    // Allow a little slack for allocator noise, but nothing proportional to the iteration count.
+    assert!(resident_pages() < baseline + 256);
+}
+
+#[test]
+#[ignore = "slow; checks that the solidity verifier source is freed after every call"]
+fn test_get_solidity_verifier_does_not_leak() {
+    init_srs();
+    let acir_composer = AcirComposer::new(16).unwrap();
+    acir_composer
+        .init_proving_key(&acir_buffer_uncompressed())
+        .unwrap();
+    acir_composer.init_verification_key().unwrap();
+
+    for _ in 0..100 {
+        acir_composer.get_solidity_verifier().unwrap();
+    }
+    let baseline = resident_pages();
+    for _ in 0..5_000 {
+        acir_composer.get_solidity_verifier().unwrap();
+    }
+    // Each leaked verifier would be tens of kilobytes, i.e. hundreds of megabytes in total.
+    assert!(resident_pages() < baseline + 256);
+}
+
+#[test]
+#[ignore = "slow; checks that verification key buffers are freed after every call"]
+fn test_get_verification_key_does_not_leak() {
+    init_srs();
+    let acir_composer = AcirComposer::new(16).unwrap();
+    acir_composer
+        .init_proving_key(&acir_buffer_uncompressed())
+        .unwrap();
+    acir_composer.init_verification_key().unwrap();
+
+    for _ in 0..100 {
+        acir_composer.get_verification_key().unwrap();
+    }
+    let baseline = resident_pages();
+    for _ in 0..50_000 {
+        acir_composer.get_verification_key().unwrap();
+    }
+    // Each leaked key would be about 1.7 kilobytes, i.e. over 80 megabytes in total.
+    assert!(resident_pages() < baseline + 256);
+}
+
+#[test]
+fn test_proof_public_inputs() {
+    let mut bytes = vec![1u8; 32];
+    bytes.extend_from_slice(&[2u8; 32]);
+    bytes.extend_from_slice(&[3u8; 16]);
+    let proof = Proof::from(bytes);
+
+    assert_eq!(proof.public_inputs(2), vec![[1u8; 32], [2u8; 32]]);
+    assert_eq!(proof.public_inputs(5).len(), 2);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_circuit_sizes_serde_round_trip() {
+    let sizes = get_circuit_sizes(&acir_buffer_uncompressed()).unwrap();
+    let json = serde_json::to_string(&sizes).unwrap();
+    assert_eq!(json, r#"{"exact":5,"total":10,"subgroup":16}"#);
+    let parsed: super::acir_composer::CircuitSizes = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed, sizes);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_proof_serde_round_trip() {
+    let proof = Proof::from(vec![0x00, 0xab, 0xff]);
+    let json = serde_json::to_string(&proof).unwrap();
+    assert_eq!(json, r#""00abff""#);
+    assert_eq!(serde_json::from_str::<Proof>(&json).unwrap(), proof);
+    assert_eq!(serde_json::from_str::<Proof>(r#""00ABFF""#).unwrap(), proof);
+    assert!(serde_json::from_str::<Proof>(r#""0ab""#).is_err());
+}
+
+/// Runs `f` and returns the `log` records it emitted on this thread.
+#[cfg(feature = "log")]
+fn capture_logs(f: impl FnOnce()) -> Vec<(log::Level, String)> {
+    use std::cell::RefCell;
+
+    thread_local! {
+        static RECORDS: RefCell<Vec<(log::Level, String)>> = const { RefCell::new(Vec::new()) };
+    }
+
+    /// Keeps each record on the thread that logged it, so concurrent tests see only their own.
+    struct ThreadLogger;
+
+    impl log::Log for ThreadLogger {
+        fn enabled(&self, _: &log::Metadata) -> bool {
+            true
+        }
+
+        fn log(&self, record: &log::Record) {
+            let entry = (record.level(), record.args().to_string());
+            RECORDS.with(|records| records.borrow_mut().push(entry));
+        }
+
+        fn flush(&self) {}
+    }
+
+    static INIT: Once = Once::new();
+    INIT.call_once(|| {
+        log::set_logger(&ThreadLogger).unwrap();
+        log::set_max_level(log::LevelFilter::Trace);
+    });
+
+    RECORDS.with(|records| records.borrow_mut().clear());
+    f();
+    RECORDS.with(|records| records.take())
+}
+
+#[cfg(feature = "log")]
+#[test]
+fn test_backend_errors_are_logged_once() {
+    let records = capture_logs(|| {
+        get_circuit_sizes(&acir_buffer_uncompressed()).unwrap();
+    });
+    assert!(records.is_empty(), "{records:?}");
+
+    let acir_composer = AcirComposer::new(16).unwrap();
+    let mut err = None;
+    let records = capture_logs(|| {
+        err = acir_composer.init_proving_key(&over_long_circuit()).err();
+    });
+    let err = err.expect("the over-long circuit is rejected by the backend");
+    assert_eq!(records, [(log::Level::Error, err.to_string())]);
+}
+
+/// Runs `f` with a subscriber that records `DEBUG` events and span closures, returning what it
+/// wrote.
+#[cfg(feature = "tracing")]
+fn capture_traces(f: impl FnOnce()) -> String {
+    use std::io;
+    use std::sync::{Arc, Mutex};
+    use tracing_subscriber::fmt::format::FmtSpan;
+
+    #[derive(Clone, Default)]
+    struct Captured(Arc<Mutex<Vec<u8>>>);
+
+    impl io::Write for Captured {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let captured = Captured::default();
+    let writer = captured.clone();
+    let subscriber = tracing_subscriber::fmt()
+        .with_max_level(tracing::Level::DEBUG)
+        .with_span_events(FmtSpan::CLOSE)
+        .with_ansi(false)
+        .with_writer(move || writer.clone())
+        .finish();
+
+    tracing::subscriber::with_default(subscriber, f);
+
+    let output = captured.0.lock().unwrap().clone();
+    String::from_utf8(output).unwrap()
+}
+
+#[cfg(feature = "tracing")]
+#[test]
+fn test_backend_errors_are_traced() {
+    let output = capture_traces(|| {
+        get_circuit_sizes(&acir_buffer_uncompressed()).unwrap();
+        let acir_composer = AcirComposer::new(16).unwrap();
+        assert!(acir_composer.init_proving_key(&over_long_circuit()).is_err());
+    });
+
+    assert!(output.contains("DEBUG") && output.contains("subgroup=16"));
+    assert!(output.contains("ERROR") && output.contains("acir_init_proving_key"));
+}
+
+#[cfg(feature = "tracing")]
+#[test]
+fn test_ffi_calls_are_traced_with_timing() {
+    init_srs();
+    let output = capture_traces(|| {
+        let acir_composer = AcirComposer::new(16).unwrap();
+        let proof = acir_composer
+            .create_proof(&acir_buffer_uncompressed(), &witness(), ProofMode::Standard)
+            .unwrap();
+        assert!(acir_composer.verify_proof(&proof, ProofMode::Standard).unwrap());
+    });
+
+    for name in ["acir_new_acir_composer", "acir_create_proof", "acir_verify_proof"] {
+        let line = output
+            .lines()
+            .find(|line| line.contains(&format!("{name}{{")) && line.contains("close"))
+            .unwrap_or_else(|| panic!("no span for {name} in:\n{output}"));
+        assert!(line.contains("duration_ms="), "{line}");
+    }
+    assert!(output.contains("inputs=cs:1393,witness:388"));
+}
@@ -0,0 +1,112 @@
+use std::fmt;
+use std::sync::Arc;
+
+use crate::ct::ct_eq;
+
+/// A serialized verification key, as returned by
+/// [`super::acir_composer::AcirComposer::get_verification_key`].
+///
+/// The key is only parsed when loaded into a composer, so a server can keep one
+/// `VerificationKey` and create a verifier from it with
+/// [`super::acir_composer::AcirComposer::with_verification_key`] for each thread, then verify any
+/// number of proofs with each verifier without parsing the key again.
+///
+/// Clones share the same bytes. Keys compare in constant time, see [`ct_eq`].
+#[derive(Debug, Clone)]
+pub struct VerificationKey(Arc<[u8]>);
+
+impl VerificationKey {
+    /// Returns a copy of the key's raw bytes.
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.0.to_vec()
+    }
+
+    /// The size of the evaluation subgroup of the key's circuit, read from the second big-endian
+    /// u32 of the key, or `None` if the key is too short to hold it.
+    pub fn circuit_size(&self) -> Option<u32> {
+        let bytes = self.0.get(4..8)?;
+        Some(u32::from_be_bytes(bytes.try_into().unwrap()))
+    }
+
+    /// The number of public inputs of the key's circuit, read from the third big-endian u32 of
+    /// the key, or `None` if the key is too short to hold it.
+    pub fn num_public_inputs(&self) -> Option<u32> {
+        let bytes = self.0.get(8..12)?;
+        Some(u32::from_be_bytes(bytes.try_into().unwrap()))
+    }
+}
+
+impl PartialEq for VerificationKey {
+    fn eq(&self, other: &Self) -> bool {
+        ct_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for VerificationKey {}
+
+impl AsRef<[u8]> for VerificationKey {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl From<Vec<u8>> for VerificationKey {
+    fn from(bytes: Vec<u8>) -> Self {
+        VerificationKey(bytes.into())
+    }
+}
+
+impl From<VerificationKey> for Vec<u8> {
+    fn from(verification_key: VerificationKey) -> Self {
+        verification_key.into_bytes()
+    }
+}
+
+/// A verification key exported as field elements, as
+/// [`super::acir_composer::AcirComposer::serialize_verification_key_into_fields`] returns it for
+/// verifying proofs of its circuit inside another circuit.
+///
+/// It displays as the two `Prover.toml` entries a Noir circuit reads them from:
+///
+/// ```toml
+/// verification_key = ["0x…", "0x…", …]
+/// key_hash = "0x…"
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerificationKeyFields {
+    /// The key's field elements, in the layout of
+    /// [`super::recursion::RecursionInputs::verification_key`].
+    pub key_fields: Vec<[u8; 32]>,
+    /// Hash of the key, which the outer circuit checks the fields against.
+    pub key_hash: [u8; 32],
+}
+
+impl VerificationKeyFields {
+    /// The key's field elements as `0x`-prefixed hex strings.
+    pub fn key_fields_hex(&self) -> Vec<String> {
+        self.key_fields.iter().map(field_hex).collect()
+    }
+
+    /// The key hash as a `0x`-prefixed hex string.
+    pub fn key_hash_hex(&self) -> String {
+        field_hex(&self.key_hash)
+    }
+}
+
+impl fmt::Display for VerificationKeyFields {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("verification_key = [")?;
+        for (i, field) in self.key_fields.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "\"{}\"", field_hex(field))?;
+        }
+        writeln!(f, "]")?;
+        write!(f, "key_hash = \"{}\"", self.key_hash_hex())
+    }
+}
+
+fn field_hex(field: &[u8; 32]) -> String {
+    format!("0x{}", hex::encode(field))
+}
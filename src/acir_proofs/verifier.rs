@@ -0,0 +1,78 @@
+use std::io::Write;
+
+use super::acir_composer::{AcirComposer, VerifierWriteError};
+use super::proof::{Proof, ProofMode};
+use super::solidity::SolidityOptions;
+use super::verification_key::VerificationKey;
+use crate::error::BackendError;
+
+/// The verifying half of an [`AcirComposer`]: it holds a verification key, and nothing that needs
+/// a proving key or constraint system can be called on it.
+#[derive(Debug)]
+pub struct AcirVerifier {
+    composer: AcirComposer,
+}
+
+impl AcirVerifier {
+    /// Creates a verifier for `verification_key`, see [`AcirComposer::with_verification_key`].
+    pub fn new(verification_key: &VerificationKey) -> Result<Self, BackendError> {
+        AcirComposer::with_verification_key(verification_key).map(Self::from_composer)
+    }
+
+    /// Wraps a composer that already holds a verification key.
+    pub(crate) fn from_composer(composer: AcirComposer) -> Self {
+        AcirVerifier { composer }
+    }
+
+    /// See [`AcirComposer::verify_proof`].
+    pub fn verify_proof(
+        &self,
+        proof: &Proof,
+        mode: impl Into<Option<ProofMode>>,
+    ) -> Result<bool, BackendError> {
+        self.composer.verify_proof(proof, mode)
+    }
+
+    /// See [`AcirComposer::verify_proofs`].
+    pub fn verify_proofs(
+        &self,
+        proofs: &[Proof],
+        mode: impl Into<Option<ProofMode>>,
+    ) -> Vec<Result<bool, BackendError>> {
+        self.composer.verify_proofs(proofs, mode)
+    }
+
+    /// See [`AcirComposer::get_solidity_verifier`].
+    pub fn get_solidity_verifier(&self) -> Result<String, BackendError> {
+        self.composer.get_solidity_verifier()
+    }
+
+    /// See [`AcirComposer::write_solidity_verifier`].
+    pub fn write_solidity_verifier<W: Write>(
+        &self,
+        writer: W,
+        options: &SolidityOptions,
+    ) -> Result<(), VerifierWriteError> {
+        self.composer.write_solidity_verifier(writer, options)
+    }
+
+    /// See [`AcirComposer::verification_key`].
+    pub fn verification_key(&self) -> Result<VerificationKey, BackendError> {
+        self.composer.verification_key()
+    }
+
+    /// See [`AcirComposer::num_public_inputs`].
+    pub fn num_public_inputs(&self) -> Result<u32, BackendError> {
+        self.composer.num_public_inputs()
+    }
+
+    /// See [`AcirComposer::circuit_size`].
+    pub fn circuit_size(&self) -> Result<u32, BackendError> {
+        self.composer.circuit_size()
+    }
+
+    /// See [`AcirComposer::close`].
+    pub fn close(self) -> Result<(), BackendError> {
+        self.composer.close()
+    }
+}
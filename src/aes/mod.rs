@@ -0,0 +1,85 @@
+use crate::error::lock_backend;
+
+#[cfg(test)]
+pub mod test;
+
+extern "C" {
+    // Exported by `crypto/aes128/c_bind.cpp`, which has no header for bindgen to read. Both
+    // overwrite `input` and `iv` as they go, and only process whole blocks of `length`.
+    fn aes__encrypt_buffer_cbc(
+        input: *mut u8,
+        iv: *mut u8,
+        key: *const u8,
+        length: usize,
+        output: *mut u8,
+    );
+    fn aes__decrypt_buffer_cbc(
+        input: *mut u8,
+        iv: *mut u8,
+        key: *const u8,
+        length: usize,
+        output: *mut u8,
+    );
+}
+
+/// Size of an AES block, and of the key and IV of AES-128.
+const BLOCK_SIZE: usize = 16;
+
+/// Encrypts `plaintext` with AES-128 in CBC mode, as Noir's `std::aes128::aes128_encrypt` does.
+///
+/// barretenberg only encrypts whole blocks, so the plaintext is first padded with PKCS#7: `n`
+/// bytes of value `n` for the `1..=16` bytes it takes to reach the next multiple of the block
+/// size, i.e. a full block of padding when `plaintext` is already a multiple of it. The
+/// ciphertext is therefore always between 1 and 16 bytes longer than `plaintext`.
+pub fn aes_cbc_encrypt(plaintext: &[u8], iv: &[u8; 16], key: &[u8; 16]) -> Vec<u8> {
+    let padding = BLOCK_SIZE - plaintext.len() % BLOCK_SIZE;
+    let mut input = plaintext.to_vec();
+    input.resize(plaintext.len() + padding, padding as u8);
+    // The backend advances the IV it is given and works in place on the input, so both are copies.
+    let mut iv = *iv;
+    let mut output = vec![0u8; input.len()];
+    let _backend = lock_backend();
+    unsafe {
+        aes__encrypt_buffer_cbc(
+            input.as_mut_ptr(),
+            iv.as_mut_ptr(),
+            key.as_ptr(),
+            input.len(),
+            output.as_mut_ptr(),
+        )
+    };
+    output
+}
+
+/// Decrypts a ciphertext written by [`aes_cbc_encrypt`] and strips its PKCS#7 padding.
+///
+/// Returns `None` if `ciphertext` is not a non-empty multiple of the block size, or does not
+/// decrypt to validly padded data, as with the wrong key or IV.
+pub fn aes_cbc_decrypt(ciphertext: &[u8], iv: &[u8; 16], key: &[u8; 16]) -> Option<Vec<u8>> {
+    if ciphertext.is_empty() || ciphertext.len() % BLOCK_SIZE != 0 {
+        return None;
+    }
+    let mut input = ciphertext.to_vec();
+    let mut iv = *iv;
+    let mut output = vec![0u8; input.len()];
+    let _backend = lock_backend();
+    unsafe {
+        aes__decrypt_buffer_cbc(
+            input.as_mut_ptr(),
+            iv.as_mut_ptr(),
+            key.as_ptr(),
+            input.len(),
+            output.as_mut_ptr(),
+        )
+    };
+    let padding = *output.last()?;
+    if padding == 0 || padding as usize > BLOCK_SIZE {
+        return None;
+    }
+    let len = output.len() - padding as usize;
+    if output[len..].iter().any(|byte| *byte != padding) {
+        return None;
+    }
+    output.truncate(len);
+    Some(output)
+}
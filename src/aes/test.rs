@@ -0,0 +1,57 @@
+use super::{aes_cbc_decrypt, aes_cbc_encrypt};
+
+/// The CBC-AES128 vector from NIST SP 800-38A, F.2.1.
+const KEY: &str = "2b7e151628aed2a6abf7158809cf4f3c";
+const IV: &str = "000102030405060708090a0b0c0d0e0f";
+const PLAINTEXT: &str = "6bc1bee22e409f96e93d7e117393172aae2d8a571e03ac9c9eb76fac45af8e51\
+                         30c81c46a35ce411e5fbc1191a0a52eff69f2445df4f9b17ad2b417be66c3710";
+const CIPHERTEXT: &str = "7649abac8119b246cee98e9b12e9197d5086cb9b507219ee95db113a917678b2\
+                          73bed6b8e3c1743b7116e69e222295163ff1caa1681fac09120eca307586e1a7";
+
+fn key_and_iv() -> ([u8; 16], [u8; 16]) {
+    let key = hex::decode(KEY).unwrap().try_into().unwrap();
+    let iv = hex::decode(IV).unwrap().try_into().unwrap();
+    (key, iv)
+}
+
+#[test]
+fn test_aes_cbc_known_answer() {
+    let (key, iv) = key_and_iv();
+    let plaintext = hex::decode(PLAINTEXT).unwrap();
+    let ciphertext = aes_cbc_encrypt(&plaintext, &iv, &key);
+    // The plaintext fills four blocks exactly, so a fifth holds only padding.
+    assert_eq!(ciphertext.len(), plaintext.len() + 16);
+    assert_eq!(hex::encode(&ciphertext[..64]), CIPHERTEXT);
+    assert_eq!(aes_cbc_decrypt(&ciphertext, &iv, &key), Some(plaintext));
+}
+
+#[test]
+fn test_aes_cbc_round_trip() {
+    let (key, iv) = key_and_iv();
+    for len in [0, 1, 15, 16, 17, 31, 100] {
+        let plaintext: Vec<u8> = (0..len).map(|i| i as u8).collect();
+        let ciphertext = aes_cbc_encrypt(&plaintext, &iv, &key);
+        assert_eq!(ciphertext.len(), (len / 16 + 1) * 16, "length {len}");
+        assert_eq!(
+            aes_cbc_decrypt(&ciphertext, &iv, &key),
+            Some(plaintext),
+            "length {len}"
+        );
+    }
+}
+
+#[test]
+fn test_aes_cbc_decrypt_rejects_bad_input() {
+    let (key, iv) = key_and_iv();
+    let ciphertext = aes_cbc_encrypt(b"attack at dawn", &iv, &key);
+    assert_eq!(aes_cbc_decrypt(&[], &iv, &key), None);
+    assert_eq!(aes_cbc_decrypt(&ciphertext[..15], &iv, &key), None);
+
+    // A wrong key usually fails the padding check, and otherwise decrypts to garbage.
+    let mut wrong_key = key;
+    wrong_key[0] ^= 1;
+    assert_ne!(
+        aes_cbc_decrypt(&ciphertext, &iv, &wrong_key),
+        Some(b"attack at dawn".to_vec())
+    );
+}
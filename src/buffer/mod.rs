@@ -1,59 +1,360 @@
-use std::{slice, ffi::CStr};
+use std::fmt;
+use std::io::{self, Read};
+use std::ops::Deref;
+use std::ptr::NonNull;
+use std::slice;
 
-pub struct Buffer {
-    data: Vec<u8>,
+use crate::dealloc::dealloc;
+
+#[cfg(test)]
+pub mod test;
+
+/// A length-prefixed byte buffer returned by barretenberg, see [`InputBuffer`] for the buffers
+/// passed to it.
+///
+/// Output buffers are allocated by the backend's `to_heap_buffer` with `aligned_alloc` and owned
+/// by the caller afterwards. `OwnedBuffer` takes that ownership and releases the allocation with
+/// the deallocator from [`crate::dealloc`] when dropped; it must never reach the Rust allocator.
+///
+/// For the same reason there is no way to turn an `OwnedBuffer` into a `Vec<u8>` without copying:
+/// `Vec::from_raw_parts` would hand a 64-byte aligned C allocation, whose data starts after a
+/// 4-byte length prefix, to the Rust allocator. Callers that only read the bytes should borrow
+/// them in place, either with [`OwnedBuffer::as_slice`] or through `Deref<Target = [u8]>`.
+pub struct OwnedBuffer {
+    ptr: NonNull<u8>,
+    /// Offset of the data from the start of the allocation, past every length prefix.
+    start: usize,
+    len: usize,
 }
 
-impl Buffer {
-    /// Constructs a Buffer from a raw pointer, reading a u32 length followed by that many bytes.
+impl OwnedBuffer {
+    /// Takes ownership of a buffer returned by barretenberg, which starts with a big-endian u32
+    /// length followed by that many bytes.
     ///
     /// # Safety
-    /// This method is unsafe because it trusts the caller to ensure that `ptr` is a valid pointer
-    /// pointing to at least `u32` bytes plus the length indicated by the u32 value.
-    pub unsafe fn from_ptr(ptr: *const u8) -> Result<Self, &'static str> {
-        if ptr.is_null() {
-            return Err("Pointer is null.");
+    /// A non-null `ptr` must have been returned by barretenberg's `to_heap_buffer`, must hold at
+    /// least the length prefix plus the number of bytes it indicates, and must not be used after
+    /// this call.
+    pub unsafe fn from_heap_ptr(ptr: *mut u8) -> Result<Self, &'static str> {
+        let ptr = NonNull::new(ptr).ok_or("Pointer is null.")?;
+        let len_slice = slice::from_raw_parts(ptr.as_ptr(), 4);
+        let len = u32::from_be_bytes(len_slice.try_into().unwrap()) as usize;
+        Ok(Self { ptr, start: 4, len })
+    }
+
+    /// Like [`OwnedBuffer::from_heap_ptr`], but rejects a buffer whose length prefix claims more
+    /// than `max_len` bytes, or no bytes at all, before anything past the prefix is read.
+    ///
+    /// A rejected buffer is still freed. Every value barretenberg serializes starts with its own
+    /// length prefix or fixed-size fields, so a successful call never returns an empty buffer.
+    ///
+    /// # Safety
+    /// A non-null `ptr` must have been returned by barretenberg's `to_heap_buffer`, must hold at
+    /// least the 4-byte length prefix, and must not be used after this call. If the prefix is
+    /// within bounds, the allocation must hold that many bytes after it.
+    pub unsafe fn from_heap_ptr_bounded(ptr: *mut u8, max_len: usize) -> Result<Self, BufferError> {
+        let buffer = Self::from_heap_ptr(ptr).map_err(|_| BufferError::Null)?;
+        if buffer.len > max_len {
+            Err(BufferError::TooLarge {
+                len: buffer.len,
+                max: max_len,
+            })
+        } else if buffer.len == 0 {
+            Err(BufferError::Empty)
+        } else {
+            Ok(buffer)
         }
+    }
 
-        // 1. Create a slice of the first 4 bytes from the pointer.
-        let len_slice = slice::from_raw_parts(ptr, 4);
+    /// Returns the buffer's data, without its length prefix, borrowed from the C allocation.
+    pub fn as_slice(&self) -> &[u8] {
+        // SAFETY: `from_heap_ptr` requires the allocation to hold `len` bytes after the prefix,
+        // `into_vector` only ever narrows that range, and it stays alive until `self` is dropped.
+        unsafe { slice::from_raw_parts(self.ptr.as_ptr().add(self.start), self.len) }
+    }
 
-        // 2. Interpret those 4 bytes as a u32 using little-endian.
-        let len = u32::from_be_bytes([len_slice[0], len_slice[1], len_slice[2], len_slice[3]]);
-        
-        // 3. Move the pointer by 4 bytes.
-        let data_ptr = ptr.add(4);
+    /// The length of the data in bytes, without its length prefix.
+    pub fn len(&self) -> usize {
+        self.len
+    }
 
-        // 4. Read the next len of bytes into a Vec<u8>.
-        let data = slice::from_raw_parts(data_ptr, len as usize);
-        Ok(Self {
-            data: data.to_vec(),
-        })
+    /// Whether the buffer holds no data.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
     }
 
-    /// Returns a reference to the buffer's data as a slice.
-    pub fn as_slice(&self) -> &[u8] {
-        &self.data
+    /// Reads the buffer's data as a serialized vector of field elements, as [`read_fields`] does.
+    ///
+    /// Fails with [`BufferError::Misaligned`] if the bytes after the element count are not a whole
+    /// number of 32-byte elements.
+    pub fn as_field_elements(&self) -> Result<Vec<[u8; 32]>, BufferError> {
+        self.check_field_elements()?;
+        Ok(self.as_slice()[4..]
+            .chunks_exact(32)
+            .map(|field| field.try_into().unwrap())
+            .collect())
+    }
+
+    /// Like [`OwnedBuffer::as_field_elements`], but narrows the buffer to the elements instead of
+    /// copying them.
+    pub fn into_field_vec(mut self) -> Result<FieldVec, BufferError> {
+        self.check_field_elements()?;
+        self.start += 4;
+        self.len -= 4;
+        Ok(FieldVec { buffer: self })
+    }
+
+    /// Iterates over the buffer's data in 32-byte field elements, copying one at a time.
+    ///
+    /// Trailing bytes that do not fill a whole element are skipped; [`OwnedBuffer::into_field_vec`]
+    /// checks that there are none.
+    pub fn field_elements(&self) -> impl Iterator<Item = [u8; 32]> + '_ {
+        self.as_slice()
+            .chunks_exact(32)
+            .map(|field| field.try_into().unwrap())
     }
 
-    /// Consumes the Buffer, returning its underlying data as a Vec<u8>.
+    /// Checks that the data is an element count followed by exactly that many field elements.
+    fn check_field_elements(&self) -> Result<(), BufferError> {
+        let Some(len) = self.len.checked_sub(4) else {
+            return Err(BufferError::Malformed(
+                "Buffer is shorter than its length prefix.",
+            ));
+        };
+        if len % 32 != 0 {
+            return Err(BufferError::Misaligned { len });
+        }
+        let count = u32::from_be_bytes(self.as_slice()[..4].try_into().unwrap());
+        if u64::from(count) * 32 != len as u64 {
+            return Err(BufferError::Malformed(
+                "Buffer does not hold as many field elements as its length prefix claims.",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Narrows the buffer to the `std::vector<uint8_t>` or `std::string` serialized at the start
+    /// of its data, as [`read_vector`] does, without copying or reallocating.
+    pub fn into_vector(mut self) -> Result<Self, &'static str> {
+        self.len = read_vector(self.as_slice())?.len();
+        self.start += 4;
+        Ok(self)
+    }
+
+    /// Consumes the OwnedBuffer, copying its data into a Vec<u8> and freeing the C allocation.
     pub fn to_vec(self) -> Vec<u8> {
-        self.data
+        self.as_slice().to_vec()
+    }
+
+    /// Encodes the buffer's data as a lowercase hex string, see [`crate::encoding::to_hex`].
+    ///
+    /// There is no decoding counterpart, as an `OwnedBuffer` only ever holds memory allocated by
+    /// the backend; decode with [`crate::encoding::from_hex`] instead.
+    #[cfg(feature = "encoding")]
+    pub fn to_hex(&self) -> String {
+        crate::encoding::to_hex(self.as_slice())
+    }
+
+    /// Encodes the buffer's data as base64, see [`crate::encoding::to_base64`].
+    #[cfg(feature = "encoding")]
+    pub fn to_base64(&self) -> String {
+        crate::encoding::to_base64(self.as_slice())
+    }
+}
+
+impl Deref for OwnedBuffer {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+/// Reading consumes the data from the front, without copying it anywhere but into `buf`, so a
+/// buffer can be streamed with [`io::copy`]. The allocation is still only freed on drop.
+impl Read for OwnedBuffer {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.len.min(buf.len());
+        buf[..n].copy_from_slice(&self.as_slice()[..n]);
+        self.start += n;
+        self.len -= n;
+        Ok(n)
     }
 }
 
-pub fn serialize_slice(data: &[u8]) -> Vec<u8> {
-    let mut buffer = Vec::new();
-    buffer.extend_from_slice(&(data.len() as u32).to_be_bytes());
-    buffer.extend_from_slice(data);
+impl AsRef<[u8]> for OwnedBuffer {
+    fn as_ref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl Drop for OwnedBuffer {
+    fn drop(&mut self) {
+        unsafe { dealloc(self.ptr.as_ptr().cast()) };
+    }
+}
+
+/// Field elements returned by barretenberg, read in place from the [`OwnedBuffer`] holding them.
+pub struct FieldVec {
+    buffer: OwnedBuffer,
+}
+
+impl FieldVec {
+    /// The number of field elements.
+    pub fn len(&self) -> usize {
+        self.buffer.len() / 32
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.buffer.is_empty()
+    }
+
+    /// Iterates over the field elements, see [`OwnedBuffer::field_elements`].
+    pub fn iter(&self) -> impl Iterator<Item = [u8; 32]> + '_ {
+        self.buffer.field_elements()
+    }
+
+    /// Returns the field elements concatenated, without the element count.
+    pub fn as_bytes(&self) -> &[u8] {
+        self.buffer.as_slice()
+    }
+
+    /// Copies the field elements into a `Vec`.
+    pub fn to_vec(&self) -> Vec<[u8; 32]> {
+        self.iter().collect()
+    }
+}
+
+impl fmt::Debug for FieldVec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+/// Why [`OwnedBuffer::from_heap_ptr_bounded`] rejected an output buffer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BufferError {
+    /// The pointer is null.
+    Null,
+    /// The length prefix is zero.
+    Empty,
+    /// The length prefix claims more than `max` bytes.
+    TooLarge { len: usize, max: usize },
+    /// The buffer should hold field elements, but `len` bytes follow the element count.
+    Misaligned { len: usize },
+    /// The buffer's contents do not match the layout they should have.
+    Malformed(&'static str),
+}
+
+impl fmt::Display for BufferError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BufferError::Null => write!(f, "Pointer is null."),
+            BufferError::Empty => write!(f, "Buffer is empty."),
+            BufferError::Misaligned { len } => {
+                write!(f, "{len} bytes are not a whole number of field elements.")
+            }
+            BufferError::Malformed(reason) => write!(f, "{reason}"),
+            BufferError::TooLarge { len, max } => {
+                write!(
+                    f,
+                    "Buffer claims {len} bytes, more than the maximum of {max}."
+                )
+            }
+        }
+    }
+}
+
+/// Reads a serialized `std::vector<uint8_t>` or `std::string` from the start of `bytes`: a
+/// big-endian u32 byte count followed by the bytes, which are borrowed rather than copied.
+///
+/// Fails if `bytes` is shorter than the length claims.
+pub fn read_vector(bytes: &[u8]) -> Result<&[u8], &'static str> {
+    if bytes.len() < 4 {
+        return Err("Buffer is shorter than its length prefix.");
+    }
+    let (len, rest) = bytes.split_at(4);
+    let len = u32::from_be_bytes(len.try_into().unwrap());
+    rest.get(..len as usize)
+        .ok_or("Buffer is shorter than its length prefix claims.")
+}
+
+/// Bytes allocated in Rust to be passed to barretenberg as a `std::vector<uint8_t>`.
+///
+/// Unlike an [`OwnedBuffer`], nothing is handed over to the backend: it only reads the serialized
+/// copy for the duration of the call, and Rust frees it afterwards.
+///
+/// Whether an entry point wants this length-prefixed form or the raw bytes depends on how its C
+/// signature reads them, not on what the bytes are:
+///
+/// - Prefixed with [`InputBuffer::serialize`], as they are read with
+///   `from_buffer<std::vector<uint8_t>>`: the constraint system of `acir_get_circuit_sizes`,
+///   `acir_init_proving_key` and `acir_create_proof`, the witness of `acir_create_proof`, the
+///   proof of `acir_verify_proof` and `acir_serialize_proof_into_fields`, the input of `blake2s`
+///   and the message of `schnorr_construct_signature` and `schnorr_verify_signature`.
+/// - Count-prefixed field elements from [`serialize_fields`], read as `std::vector<fr>`: the
+///   inputs of the Pedersen functions.
+/// - Raw, since the backend deserializes them field by field or is passed their length: the
+///   verification key of `acir_load_verification_key`, the points of `srs_init_srs`, the
+///   message of `ethash_keccak256` and the `ecdsa__*` functions, and every fixed-size key,
+///   signature or field element.
+///
+/// Every output buffer is an [`OwnedBuffer`] behind the backend's own prefix; the value inside it
+/// may be prefixed once more, which [`read_vector`] and [`OwnedBuffer::into_vector`] strip.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InputBuffer<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> InputBuffer<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        InputBuffer { data }
+    }
+
+    /// Serializes the bytes as a `std::vector<uint8_t>`: a big-endian u32 byte count followed by
+    /// the bytes, which [`read_vector`] reads back.
+    ///
+    /// This is the layout barretenberg's `from_buffer<std::vector<uint8_t>>` expects on every
+    /// host.
+    pub fn serialize(&self) -> Vec<u8> {
+        let mut buffer = Vec::with_capacity(4 + self.data.len());
+        buffer.extend_from_slice(&(self.data.len() as u32).to_be_bytes());
+        buffer.extend_from_slice(self.data);
+        buffer
+    }
+}
+
+impl<'a> From<&'a [u8]> for InputBuffer<'a> {
+    fn from(data: &'a [u8]) -> Self {
+        InputBuffer::new(data)
+    }
+}
+
+/// Serializes field elements as a vector: a big-endian u32 element count followed by the elements.
+pub fn serialize_fields(fields: &[[u8; 32]]) -> Vec<u8> {
+    let mut buffer = Vec::with_capacity(4 + fields.len() * 32);
+    buffer.extend_from_slice(&(fields.len() as u32).to_be_bytes());
+    for field in fields {
+        buffer.extend_from_slice(field);
+    }
     buffer
 }
 
-pub fn parse_c_str(ptr: *const ::std::os::raw::c_char) -> Option<String> {
-    if ptr.is_null() {
-        return None;
+/// Reads field elements serialized by [`serialize_fields`]: a big-endian u32 element count
+/// followed by the 32-byte elements.
+///
+/// Fails unless `bytes` holds exactly that many elements.
+pub fn read_fields(bytes: &[u8]) -> Result<Vec<[u8; 32]>, &'static str> {
+    if bytes.len() < 4 {
+        return Err("Buffer is shorter than its length prefix.");
     }
-    unsafe { CStr::from_ptr(ptr) }
-        .to_str()
-        .map_or(None, |s| Some(s.to_string()))
-}
\ No newline at end of file
+    let (len, rest) = bytes.split_at(4);
+    let len = u32::from_be_bytes(len.try_into().unwrap());
+    if rest.len() as u64 != u64::from(len) * 32 {
+        return Err("Buffer does not hold as many field elements as its length prefix claims.");
+    }
+    Ok(rest
+        .chunks_exact(32)
+        .map(|field| field.try_into().unwrap())
+        .collect())
+}
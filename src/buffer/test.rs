@@ -0,0 +1,253 @@
+use std::ffi::c_void;
+use std::io::Read;
+use std::time::Instant;
+
+use proptest::prelude::*;
+
+use super::{read_fields, read_vector, serialize_fields, BufferError, InputBuffer, OwnedBuffer};
+
+extern "C" {
+    fn aligned_alloc(alignment: usize, size: usize) -> *mut c_void;
+}
+
+/// Copies `bytes` into a C allocation laid out like barretenberg's `to_heap_buffer` output.
+fn heap_buffer(bytes: &[u8]) -> *mut u8 {
+    let serialized = InputBuffer::new(bytes).serialize();
+    // `aligned_alloc` requires the size to be a multiple of the alignment.
+    let size = (serialized.len() + 63) / 64 * 64;
+    unsafe {
+        let ptr = aligned_alloc(64, size) as *mut u8;
+        ptr.copy_from_nonoverlapping(serialized.as_ptr(), serialized.len());
+        ptr
+    }
+}
+
+#[cfg(feature = "encoding")]
+#[test]
+fn test_buffer_encodings() {
+    let buffer = unsafe { OwnedBuffer::from_heap_ptr(heap_buffer(&[0x00, 0xab, 0xff])) }.unwrap();
+    assert_eq!(buffer.to_hex(), "00abff");
+    assert_eq!(buffer.to_base64(), "AKv/");
+}
+
+#[test]
+fn test_buffer_borrows_heap_allocation() {
+    let buffer = unsafe { OwnedBuffer::from_heap_ptr(heap_buffer(b"proof")) }.unwrap();
+    let borrowed = buffer.as_slice();
+    assert_eq!(borrowed, b"proof");
+    assert_eq!(borrowed.as_ptr(), unsafe { buffer.ptr.as_ptr().add(4) });
+}
+
+#[test]
+fn test_buffer_to_vec_outlives_heap_allocation() {
+    let buffer = unsafe { OwnedBuffer::from_heap_ptr(heap_buffer(b"proof")) }.unwrap();
+    // `to_vec` frees the C allocation, so the copy must not depend on it.
+    let bytes = buffer.to_vec();
+    assert_eq!(bytes, b"proof");
+}
+
+#[test]
+fn test_buffer_derefs_to_its_data() {
+    let buffer = unsafe { OwnedBuffer::from_heap_ptr(heap_buffer(b"proof")) }.unwrap();
+    assert_eq!(&buffer[..], b"proof");
+    assert_eq!(buffer.as_ref(), b"proof");
+    assert_eq!(buffer.len(), 5);
+    assert!(!buffer.is_empty());
+
+    let empty = unsafe { OwnedBuffer::from_heap_ptr(heap_buffer(b"")) }.unwrap();
+    assert!(empty.is_empty());
+}
+
+#[test]
+fn test_buffer_into_vector_narrows_in_place() {
+    let buffer =
+        unsafe { OwnedBuffer::from_heap_ptr(heap_buffer(&InputBuffer::new(b"vk").serialize())) }
+            .unwrap();
+    let data = buffer.as_slice().as_ptr();
+    let vector = buffer.into_vector().unwrap();
+    assert_eq!(&vector[..], b"vk");
+    assert_eq!(vector.as_ptr(), unsafe { data.add(4) });
+
+    let truncated = unsafe { OwnedBuffer::from_heap_ptr(heap_buffer(&[0, 0, 0, 9, 1])) }.unwrap();
+    assert!(truncated.into_vector().is_err());
+}
+
+#[test]
+#[ignore = "benchmark; compares borrowing a 64 KiB buffer with copying it"]
+fn test_borrowing_a_large_buffer_beats_copying_it() {
+    let buffer = unsafe { OwnedBuffer::from_heap_ptr(heap_buffer(&vec![7u8; 64 * 1024])) }.unwrap();
+
+    let start = Instant::now();
+    let mut copied = 0;
+    for _ in 0..10_000 {
+        copied += std::hint::black_box(buffer.as_slice().to_vec()).len();
+    }
+    let copying = start.elapsed();
+
+    let start = Instant::now();
+    let mut borrowed = 0;
+    for _ in 0..10_000 {
+        borrowed += std::hint::black_box(&buffer[..]).len();
+    }
+    let borrowing = start.elapsed();
+
+    println!("64 KiB x 10000: copying {copying:?}, borrowing {borrowing:?}");
+    assert_eq!(copied, borrowed);
+    assert!(borrowing < copying);
+}
+
+/// A C allocation holding only a length prefix that claims `claimed_len` bytes.
+fn lying_heap_buffer(claimed_len: u32) -> *mut u8 {
+    unsafe {
+        let ptr = aligned_alloc(64, 64) as *mut u8;
+        ptr.copy_from_nonoverlapping(claimed_len.to_be_bytes().as_ptr(), 4);
+        ptr
+    }
+}
+
+#[test]
+fn test_bounded_buffer_checks_its_length_prefix() {
+    let buffer = unsafe { OwnedBuffer::from_heap_ptr_bounded(heap_buffer(b"proof"), 5) }.unwrap();
+    assert_eq!(&buffer[..], b"proof");
+
+    let err = unsafe { OwnedBuffer::from_heap_ptr_bounded(heap_buffer(b"proof"), 4) }.err();
+    assert_eq!(err, Some(BufferError::TooLarge { len: 5, max: 4 }));
+
+    // Only the prefix of an oversized buffer is read, so a lie about its length is harmless.
+    let err =
+        unsafe { OwnedBuffer::from_heap_ptr_bounded(lying_heap_buffer(u32::MAX), 1 << 20) }.err();
+    assert_eq!(
+        err,
+        Some(BufferError::TooLarge {
+            len: u32::MAX as usize,
+            max: 1 << 20
+        })
+    );
+
+    let err = unsafe { OwnedBuffer::from_heap_ptr_bounded(lying_heap_buffer(0), 1 << 20) }.err();
+    assert_eq!(err, Some(BufferError::Empty));
+
+    let err = unsafe { OwnedBuffer::from_heap_ptr_bounded(std::ptr::null_mut(), 1 << 20) }.err();
+    assert_eq!(err, Some(BufferError::Null));
+}
+
+#[test]
+fn test_buffer_field_elements_round_trip() {
+    let fields = [[1u8; 32], [2u8; 32], [3u8; 32]];
+    let serialized = serialize_fields(&fields);
+    let buffer = unsafe { OwnedBuffer::from_heap_ptr(heap_buffer(&serialized)) }.unwrap();
+
+    let elements = buffer.as_field_elements().unwrap();
+    assert_eq!(elements, fields);
+    assert_eq!(elements.concat(), &serialized[4..]);
+}
+
+#[test]
+fn test_buffer_field_elements_must_be_aligned() {
+    let mut serialized = serialize_fields(&[[1u8; 32]]);
+    serialized.pop();
+    let buffer = unsafe { OwnedBuffer::from_heap_ptr(heap_buffer(&serialized)) }.unwrap();
+    assert_eq!(
+        buffer.as_field_elements(),
+        Err(BufferError::Misaligned { len: 31 })
+    );
+
+    // Aligned, but holding fewer elements than the count claims.
+    let mut serialized = serialize_fields(&[[1u8; 32], [2u8; 32]]);
+    serialized.truncate(4 + 32);
+    let buffer = unsafe { OwnedBuffer::from_heap_ptr(heap_buffer(&serialized)) }.unwrap();
+    assert!(matches!(
+        buffer.as_field_elements(),
+        Err(BufferError::Malformed(_))
+    ));
+}
+
+#[test]
+fn test_buffer_field_elements_iterator() {
+    let bytes: Vec<u8> = (0..100).collect();
+    let buffer = unsafe { OwnedBuffer::from_heap_ptr(heap_buffer(&bytes)) }.unwrap();
+    assert_eq!(buffer.field_elements().count(), buffer.len() / 32);
+    // The four trailing bytes are skipped.
+    let elements: Vec<_> = buffer.field_elements().collect();
+    assert_eq!(elements.concat(), &bytes[..96]);
+}
+
+#[test]
+fn test_buffer_into_field_vec() {
+    let fields = [[1u8; 32], [2u8; 32], [3u8; 32]];
+    let serialized = serialize_fields(&fields);
+    let buffer = unsafe { OwnedBuffer::from_heap_ptr(heap_buffer(&serialized)) }.unwrap();
+
+    let field_vec = buffer.into_field_vec().unwrap();
+    assert_eq!(field_vec.len(), 3);
+    assert_eq!(field_vec.iter().count(), field_vec.as_bytes().len() / 32);
+    assert_eq!(field_vec.to_vec(), fields);
+    assert_eq!(field_vec.as_bytes(), &serialized[4..]);
+
+    let mut serialized = serialize_fields(&fields);
+    serialized.pop();
+    let buffer = unsafe { OwnedBuffer::from_heap_ptr(heap_buffer(&serialized)) }.unwrap();
+    assert!(matches!(
+        buffer.into_field_vec(),
+        Err(BufferError::Misaligned { len: 95 })
+    ));
+}
+
+#[test]
+fn test_buffer_reads_its_data_in_chunks() {
+    let mut buffer = unsafe { OwnedBuffer::from_heap_ptr(heap_buffer(b"proof bytes")) }.unwrap();
+    let mut chunk = [0u8; 4];
+    assert_eq!(buffer.read(&mut chunk).unwrap(), 4);
+    assert_eq!(&chunk, b"proo");
+    assert_eq!(&buffer[..], b"f bytes");
+
+    let mut rest = Vec::new();
+    buffer.read_to_end(&mut rest).unwrap();
+    assert_eq!(rest, b"f bytes");
+    assert!(buffer.is_empty());
+    assert_eq!(buffer.read(&mut chunk).unwrap(), 0);
+}
+
+#[test]
+fn test_buffer_rejects_null() {
+    assert!(unsafe { OwnedBuffer::from_heap_ptr(std::ptr::null_mut()) }.is_err());
+}
+
+#[test]
+fn test_read_vector_round_trips_input_buffer() {
+    assert_eq!(
+        read_vector(&InputBuffer::new(b"proof").serialize()).unwrap(),
+        b"proof"
+    );
+}
+
+proptest! {
+    #[test]
+    fn prop_input_buffer_round_trips(bytes in prop::collection::vec(any::<u8>(), 0..4096)) {
+        let serialized = InputBuffer::new(&bytes).serialize();
+        prop_assert_eq!(serialized.len(), 4 + bytes.len());
+        prop_assert_eq!(read_vector(&serialized).unwrap(), bytes.as_slice());
+
+        let buffer = unsafe { OwnedBuffer::from_heap_ptr(heap_buffer(&bytes)) }.unwrap();
+        prop_assert_eq!(&buffer[..], bytes.as_slice());
+    }
+}
+
+#[test]
+fn test_read_vector_rejects_truncated_buffers() {
+    assert!(read_vector(&[0, 0]).is_err());
+
+    let mut bytes = InputBuffer::new(b"proof").serialize();
+    bytes.pop();
+    assert!(read_vector(&bytes).is_err());
+}
+
+#[test]
+fn test_read_fields_round_trip() {
+    let fields = [[1u8; 32], [2u8; 32]];
+    let serialized = serialize_fields(&fields);
+    assert_eq!(read_fields(&serialized).unwrap(), fields);
+
+    assert!(read_fields(&serialized[..serialized.len() - 1]).is_err());
+    assert!(read_fields(&[serialized.as_slice(), &[0]].concat()).is_err());
+}
@@ -0,0 +1,21 @@
+use std::hint::black_box;
+
+#[cfg(test)]
+pub mod test;
+
+/// Compares two byte strings in time that depends only on their lengths, not on their contents
+/// or on where they first differ, e.g. to check a verification key received from a client against
+/// a trusted one.
+///
+/// Lengths are not secret: inputs of different lengths compare unequal immediately.
+pub fn ct_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    // `black_box` keeps the optimizer from turning the accumulation into an early exit.
+    let diff = a
+        .iter()
+        .zip(b)
+        .fold(0u8, |diff, (x, y)| black_box(diff | (x ^ y)));
+    diff == 0
+}
@@ -0,0 +1,40 @@
+use std::time::{Duration, Instant};
+
+use super::ct_eq;
+
+#[test]
+fn test_ct_eq() {
+    assert!(ct_eq(b"", b""));
+    assert!(ct_eq(b"verification key", b"verification key"));
+    assert!(!ct_eq(b"verification key", b"verification kez"));
+    assert!(!ct_eq(b"verification key", b"werification key"));
+    assert!(!ct_eq(b"verification key", b"verification ke"));
+    assert!(!ct_eq(b"", b"\0"));
+}
+
+/// Fastest of several runs of `ct_eq` on `a` and a copy of it with the byte at `index` flipped.
+/// Runs for different indices are interleaved and reuse the same copy, so that warm-up, noise and
+/// memory placement affect them alike.
+fn min_times(a: &[u8], indices: [usize; 2]) -> [Duration; 2] {
+    let mut b = a.to_vec();
+    let mut times = [Duration::MAX; 2];
+    for _ in 0..50 {
+        for (time, &index) in times.iter_mut().zip(&indices) {
+            b[index] ^= 1;
+            let start = Instant::now();
+            assert!(!ct_eq(a, &b));
+            *time = (*time).min(start.elapsed());
+            b[index] ^= 1;
+        }
+    }
+    times
+}
+
+#[test]
+#[ignore = "timing-sensitive; checks that ct_eq does not exit at the first difference"]
+fn test_ct_eq_time_does_not_depend_on_first_difference() {
+    let a = vec![0x5a; 1 << 20];
+    let [early, late] = min_times(&a, [0, a.len() - 1]);
+    let ratio = early.as_secs_f64() / late.as_secs_f64();
+    assert!((0.5..2.0).contains(&ratio), "{early:?} vs {late:?}");
+}
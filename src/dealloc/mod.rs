@@ -0,0 +1,53 @@
+use std::ffi::c_void;
+use std::mem;
+use std::sync::RwLock;
+
+#[cfg(test)]
+pub mod test;
+
+/// A function that releases a buffer barretenberg allocated and handed over to the caller.
+///
+/// It is called from Rust when the buffer is dropped, never by barretenberg, but through the C ABI,
+/// so it must not unwind: a panic escaping a Rust deallocator aborts the process.
+pub type Deallocator = unsafe extern "C" fn(ptr: *mut c_void);
+
+extern "C" {
+    #[cfg(not(windows))]
+    fn free(ptr: *mut c_void);
+    #[cfg(windows)]
+    fn _aligned_free(ptr: *mut c_void);
+}
+
+/// The counterpart of the allocator barretenberg's `to_heap_buffer` uses for every output buffer:
+/// it allocates with `aligned_alloc`, which `common/mem.hpp` pairs with the C library's `free`,
+/// or with `_aligned_malloc` and `_aligned_free` on Windows.
+///
+/// `bbfree` is not an alternative. It only releases slabs handed out by `bbmalloc`, and silently
+/// ignores any other pointer, so every buffer passed to it would leak.
+#[cfg(not(windows))]
+pub const DEFAULT_DEALLOCATOR: Deallocator = free;
+#[cfg(windows)]
+pub const DEFAULT_DEALLOCATOR: Deallocator = _aligned_free;
+
+static DEALLOCATOR: RwLock<Deallocator> = RwLock::new(DEFAULT_DEALLOCATOR);
+
+/// Replaces the function every [`crate::buffer::OwnedBuffer`] is released with, returning the
+/// previous one. Only needed when linking a barretenberg build whose `to_heap_buffer` allocates
+/// with something other than [`DEFAULT_DEALLOCATOR`]'s counterpart.
+///
+/// # Safety
+/// `dealloc` must release memory returned by the linked barretenberg's `to_heap_buffer`, including
+/// buffers taken from it before this call that are still alive.
+pub unsafe fn set_deallocator(dealloc: Deallocator) -> Deallocator {
+    mem::replace(&mut *DEALLOCATOR.write().unwrap(), dealloc)
+}
+
+/// Releases `ptr` with the current deallocator.
+///
+/// # Safety
+/// `ptr` must have been returned by barretenberg's `to_heap_buffer` and must not be used after
+/// this call.
+pub(crate) unsafe fn dealloc(ptr: *mut c_void) {
+    let dealloc = *DEALLOCATOR.read().unwrap();
+    dealloc(ptr)
+}
@@ -0,0 +1,78 @@
+use std::ffi::c_void;
+use std::process::{Command, ExitStatus};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use super::{dealloc, set_deallocator, DEFAULT_DEALLOCATOR};
+use crate::acir_proofs::acir_composer::AcirComposer;
+use crate::acir_proofs::test::{acir_buffer_uncompressed, init_srs};
+
+static DEALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe extern "C" fn counting_dealloc(ptr: *mut c_void) {
+    DEALLOCATIONS.fetch_add(1, Ordering::SeqCst);
+    DEFAULT_DEALLOCATOR(ptr)
+}
+
+#[test]
+fn test_backend_buffers_are_released_with_the_deallocator() {
+    init_srs();
+    let acir_composer = AcirComposer::new(16).unwrap();
+    acir_composer
+        .init_proving_key(&acir_buffer_uncompressed())
+        .unwrap();
+    acir_composer.init_verification_key().unwrap();
+
+    // Other tests may release buffers meanwhile; the counting deallocator still frees them.
+    let previous = unsafe { set_deallocator(counting_dealloc) };
+    let before = DEALLOCATIONS.load(Ordering::SeqCst);
+    let verification_key = acir_composer.get_verification_key().unwrap();
+    let after = DEALLOCATIONS.load(Ordering::SeqCst);
+    unsafe { set_deallocator(previous) };
+
+    assert!(!verification_key.is_empty());
+    assert!(after > before);
+}
+
+/// Set in the environment of a test binary started by [`run_in_child`].
+const CHILD_ENV: &str = "BARRETENBERG_SYS_TEST_CHILD";
+
+/// Whether this process was started by [`run_in_child`] to take a path that kills it.
+pub(crate) fn is_child() -> bool {
+    std::env::var_os(CHILD_ENV).is_some()
+}
+
+/// Runs only the test `name` in a new process of this test binary, for paths that must end the
+/// process, and returns how that process exited.
+pub(crate) fn run_in_child(name: &str) -> ExitStatus {
+    Command::new(std::env::current_exe().unwrap())
+        .args(["--exact", name, "--test-threads=1", "--include-ignored"])
+        .env(CHILD_ENV, "1")
+        .status()
+        .unwrap()
+}
+
+/// Asserts the process ended with `SIGABRT` rather than exiting, or carrying on after an unwind.
+#[cfg(unix)]
+pub(crate) fn assert_aborted(status: ExitStatus) {
+    use std::os::unix::process::ExitStatusExt;
+    assert_eq!(status.signal(), Some(6), "{status}");
+}
+
+unsafe extern "C" fn panicking_dealloc(_: *mut c_void) {
+    panic!("deallocator panicked");
+}
+
+#[cfg(unix)]
+#[test]
+fn test_panicking_deallocator_aborts() {
+    if is_child() {
+        unsafe {
+            set_deallocator(panicking_dealloc);
+            dealloc(std::ptr::null_mut());
+        }
+        unreachable!("the panic must not unwind out of the deallocator");
+    }
+    assert_aborted(run_in_child(
+        "dealloc::test::test_panicking_deallocator_aborts",
+    ));
+}
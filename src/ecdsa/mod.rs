@@ -0,0 +1,4 @@
+pub mod secp256k1;
+
+#[cfg(test)]
+pub mod test;
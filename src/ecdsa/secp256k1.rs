@@ -0,0 +1,115 @@
+use crate::{
+    ecdsa__recover_public_key_from_signature, ecdsa__verify_signature, error::lock_backend,
+};
+
+/// Order of the secp256k1 group, big-endian.
+const ORDER: [u8; 32] = [
+    0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xfe,
+    0xba, 0xae, 0xdc, 0xe6, 0xaf, 0x48, 0xa0, 0x3b, 0xbf, 0xd2, 0x5e, 0x8c, 0xd0, 0x36, 0x41, 0x41,
+];
+
+/// `ORDER / 2`, the largest `s` barretenberg accepts.
+const HALF_ORDER: [u8; 32] = [
+    0x7f, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+    0x5d, 0x57, 0x6e, 0x73, 0x57, 0xa4, 0x50, 0x1d, 0xdf, 0xe9, 0x2f, 0x46, 0x68, 0x1b, 0x20, 0xa0,
+];
+
+/// Offset barretenberg adds to its recovery id to form `v`, as Ethereum does.
+const V_OFFSET: u8 = 27;
+
+/// Converts an Ethereum recovery id, the parity of the y coordinate of the signature's `R`, into
+/// barretenberg's `v`.
+///
+/// barretenberg derives its recovery bit as `y_parity ^ is_s_low`, so for the low-s signatures it
+/// accepts the bit is the inverse of Ethereum's, and `v = 27` selects an odd `R.y`.
+fn to_v(recovery_id: u8) -> u8 {
+    V_OFFSET + (recovery_id ^ 1)
+}
+
+/// Checks the `r || s` signature `sig` of `message` against the `x || y` public key `pub_key`,
+/// and that `recovery_id` recovers that same key.
+///
+/// barretenberg hashes `message` with SHA-256 itself, so this verifies signatures over
+/// `SHA-256(message)`, not over a caller-supplied digest. Signatures whose `s` is in the upper
+/// half of the group order, which barretenberg does not accept, are reported as invalid.
+pub fn verify_signature(
+    message: &[u8],
+    pub_key: &[u8; 64],
+    sig: &[u8; 64],
+    recovery_id: u8,
+) -> bool {
+    let Some((r, s)) = split_signature(sig) else {
+        return false;
+    };
+    let v = 0;
+    let valid = {
+        let _backend = lock_backend();
+        unsafe {
+            ecdsa__verify_signature(
+                message.as_ptr(),
+                message.len(),
+                pub_key.as_ptr(),
+                r.as_ptr(),
+                s.as_ptr(),
+                &v,
+            )
+        }
+    };
+    // The lock is released first, as recovery takes it again.
+    valid && recover_public_key(message, sig, recovery_id).as_ref() == Some(pub_key)
+}
+
+/// Recovers the `x || y` public key that produced the `r || s` signature `sig` of `message`, with
+/// `recovery_id` selecting among the candidate keys: 0 for an even and 1 for an odd y coordinate of
+/// the signature's `R`, as in Ethereum's `v = 27 + recovery_id`.
+///
+/// Only recovery ids 0 and 1 are supported. Ids 2 and 3 stand for an x coordinate of `r` plus the
+/// group order, which occurs with negligible probability and which barretenberg asserts against.
+///
+/// Like [`verify_signature`], `message` is hashed with SHA-256 by barretenberg. Returns `None`
+/// if the signature or recovery id is out of range, or if no key verifies the signature.
+pub fn recover_public_key(message: &[u8], sig: &[u8; 64], recovery_id: u8) -> Option<[u8; 64]> {
+    let (r, s) = split_signature(sig)?;
+    if recovery_id > 1 {
+        return None;
+    }
+    let mut v = to_v(recovery_id);
+    let mut pub_key = [0u8; 64];
+    let _backend = lock_backend();
+    unsafe {
+        ecdsa__recover_public_key_from_signature(
+            message.as_ptr(),
+            message.len(),
+            r.as_ptr(),
+            s.as_ptr(),
+            &mut v,
+            pub_key.as_mut_ptr(),
+        )
+    };
+    // Recovery does not check that `r` is the x coordinate of a curve point, so a key is only
+    // returned if it actually verifies the signature.
+    let valid = unsafe {
+        ecdsa__verify_signature(
+            message.as_ptr(),
+            message.len(),
+            pub_key.as_ptr(),
+            r.as_ptr(),
+            s.as_ptr(),
+            &v,
+        )
+    };
+    valid.then_some(pub_key)
+}
+
+/// Splits `sig` into `r` and `s`, or returns `None` if either is zero, `r` is not below the
+/// group order or `s` is above half of it.
+///
+/// barretenberg throws a C++ exception for these instead of returning an error, which would
+/// unwind across the FFI boundary, so they are rejected before calling it.
+fn split_signature(sig: &[u8; 64]) -> Option<([u8; 32], [u8; 32])> {
+    let r: [u8; 32] = sig[..32].try_into().unwrap();
+    let s: [u8; 32] = sig[32..].try_into().unwrap();
+    // Big-endian byte arrays compare like the integers they encode.
+    let in_range = r != [0; 32] && r < ORDER && s != [0; 32] && s <= HALF_ORDER;
+    in_range.then_some((r, s))
+}
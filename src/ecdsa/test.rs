@@ -0,0 +1,65 @@
+use super::secp256k1::{recover_public_key, verify_signature};
+
+const MESSAGE: &[u8] = b"The quick brown dog jumped over the lazy fox.";
+
+/// Public key of the signatures below, generated and cross-checked with OpenSSL.
+const PUB_KEY: &str = "52a16c45970bf5b44c2e3dcde78f84abc2eeb9d31365a69af7744da9ffb56635\
+                       b5c96d823f11c576227f8c88f024e8f568696b153ac6537c78d91653dfe0171e";
+
+/// Low-s signatures of `SHA-256(MESSAGE)` with recovery ids 0 and 1, i.e. `v` of 27 and 28.
+const SIG_RECOVERY_ID_0: &str = "3f264a873d551fc758e7937965b5ff440eee0b7182265240dba111dd0b49bf77\
+                                 7adaf91774c801634857fe127466df6628c385c7366c0b8f87075536de9d849d";
+const SIG_RECOVERY_ID_1: &str = "c284f5114a55af390ffea367776912347f2cdf760e921ac710f2f045888e8824\
+                                 15826798373ed6f9ecfa1f75866af5062507de0f8f65479e427b240577f79ff3";
+
+fn decode<const N: usize>(hex: &str) -> [u8; N] {
+    hex::decode(hex).unwrap().try_into().unwrap()
+}
+
+#[test]
+fn test_secp256k1_recover_public_key() {
+    let pub_key = decode(PUB_KEY);
+    for (sig, recovery_id) in [(SIG_RECOVERY_ID_0, 0), (SIG_RECOVERY_ID_1, 1)] {
+        let sig = decode(sig);
+        assert_eq!(
+            recover_public_key(MESSAGE, &sig, recovery_id),
+            Some(pub_key)
+        );
+        // The other parity recovers a different key.
+        assert_ne!(
+            recover_public_key(MESSAGE, &sig, recovery_id ^ 1),
+            Some(pub_key)
+        );
+    }
+}
+
+#[test]
+fn test_secp256k1_verify_signature() {
+    let pub_key = decode(PUB_KEY);
+    let sig = decode(SIG_RECOVERY_ID_0);
+    assert!(verify_signature(MESSAGE, &pub_key, &sig, 0));
+    assert!(!verify_signature(MESSAGE, &pub_key, &sig, 1));
+    assert!(!verify_signature(b"another message", &pub_key, &sig, 0));
+
+    let mut tampered = sig;
+    tampered[63] ^= 1;
+    assert!(!verify_signature(MESSAGE, &pub_key, &tampered, 0));
+}
+
+#[test]
+fn test_secp256k1_rejects_out_of_range_signatures() {
+    let pub_key = decode(PUB_KEY);
+    let sig: [u8; 64] = decode(SIG_RECOVERY_ID_0);
+
+    // The same signature with `s` negated, which barretenberg would throw on.
+    let mut high_s = sig;
+    high_s[32..].copy_from_slice(&decode::<32>(
+        "852506e88b37fe9cb7a801ed8b99209891eb571f78dc94ac38cb0955f198bca4",
+    ));
+    assert!(!verify_signature(MESSAGE, &pub_key, &high_s, 1));
+    assert_eq!(recover_public_key(MESSAGE, &high_s, 1), None);
+
+    assert!(!verify_signature(MESSAGE, &pub_key, &[0; 64], 0));
+    assert_eq!(recover_public_key(MESSAGE, &[0xff; 64], 0), None);
+    assert_eq!(recover_public_key(MESSAGE, &sig, 2), None);
+}
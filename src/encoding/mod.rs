@@ -0,0 +1,66 @@
+use std::fmt;
+
+use base64::{engine::general_purpose, Engine};
+
+#[cfg(test)]
+pub mod test;
+
+/// Encodes `bytes` as a lowercase hex string, without a `0x` prefix.
+pub fn to_hex(bytes: &[u8]) -> String {
+    hex::encode(bytes)
+}
+
+/// Decodes a hex string written by [`to_hex`], in either case.
+pub fn from_hex(hex_string: &str) -> Result<Vec<u8>, DecodeError> {
+    Ok(hex::decode(hex_string)?)
+}
+
+/// Encodes `bytes` as standard, padded base64.
+pub fn to_base64(bytes: &[u8]) -> String {
+    general_purpose::STANDARD.encode(bytes)
+}
+
+/// Decodes standard, padded base64 written by [`to_base64`].
+pub fn from_base64(base64_string: &str) -> Result<Vec<u8>, DecodeError> {
+    Ok(general_purpose::STANDARD.decode(base64_string)?)
+}
+
+/// Errors from [`from_hex`] and [`from_base64`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum DecodeError {
+    /// The input is not valid hex: it has an odd number of digits, or a character that is not one.
+    Hex(hex::FromHexError),
+    /// The input is not valid base64: it has a character outside the alphabet, or a bad length or
+    /// padding.
+    Base64(base64::DecodeError),
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::Hex(err) => write!(f, "invalid hex: {err}"),
+            DecodeError::Base64(err) => write!(f, "invalid base64: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            DecodeError::Hex(err) => Some(err),
+            DecodeError::Base64(err) => Some(err),
+        }
+    }
+}
+
+impl From<hex::FromHexError> for DecodeError {
+    fn from(err: hex::FromHexError) -> Self {
+        DecodeError::Hex(err)
+    }
+}
+
+impl From<base64::DecodeError> for DecodeError {
+    fn from(err: base64::DecodeError) -> Self {
+        DecodeError::Base64(err)
+    }
+}
@@ -0,0 +1,60 @@
+use super::{from_base64, from_hex, to_base64, to_hex, DecodeError};
+use crate::acir_proofs::proof::Proof;
+
+#[test]
+fn test_hex_round_trips() {
+    let bytes = [0x00, 0xab, 0xff];
+    assert_eq!(to_hex(&bytes), "00abff");
+    assert_eq!(from_hex("00abff").unwrap(), bytes);
+    assert_eq!(from_hex("00ABFF").unwrap(), bytes);
+    assert_eq!(from_hex("").unwrap(), Vec::<u8>::new());
+}
+
+#[test]
+fn test_base64_round_trips() {
+    let bytes = [0x00, 0xab, 0xff, 0x10];
+    assert_eq!(to_base64(&bytes), "AKv/EA==");
+    assert_eq!(from_base64("AKv/EA==").unwrap(), bytes);
+}
+
+#[test]
+fn test_from_hex_rejects_odd_length() {
+    assert_eq!(
+        from_hex("0ab"),
+        Err(DecodeError::Hex(hex::FromHexError::OddLength))
+    );
+}
+
+#[test]
+fn test_from_hex_rejects_invalid_character() {
+    assert_eq!(
+        from_hex("0x00"),
+        Err(DecodeError::Hex(hex::FromHexError::InvalidHexCharacter {
+            c: 'x',
+            index: 1
+        }))
+    );
+}
+
+#[test]
+fn test_from_base64_rejects_invalid_input() {
+    assert!(matches!(
+        from_base64("AKv/EA="),
+        Err(DecodeError::Base64(_))
+    ));
+    assert_eq!(
+        from_base64("AK*/EA=="),
+        Err(DecodeError::Base64(base64::DecodeError::InvalidByte(
+            2, b'*'
+        )))
+    );
+}
+
+#[test]
+fn test_proof_encodings_round_trip() {
+    let proof = Proof::from(vec![0x00, 0xab, 0xff]);
+    assert_eq!(proof.to_hex(), "00abff");
+    assert_eq!(Proof::from_hex(&proof.to_hex()).unwrap(), proof);
+    assert_eq!(Proof::from_base64(&proof.to_base64()).unwrap(), proof);
+    assert!(Proof::from_hex("abc").is_err());
+}
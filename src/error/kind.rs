@@ -0,0 +1,122 @@
+use super::BackendError;
+
+/// What a [`BackendError`] means for the caller, derived from barretenberg's free-form message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BackendErrorKind {
+    /// No CRS was loaded, or it holds too few points for the circuit.
+    CrsTooSmall,
+    /// The circuit, or the size hint it was built with, does not fit in memory.
+    CircuitTooLarge,
+    /// An input buffer could not be deserialized or does not describe a valid value.
+    MalformedInput,
+    /// A failure no rule matches, with the message it was classified from.
+    Unknown(String),
+}
+
+/// A known barretenberg failure message and what it means.
+struct Rule {
+    /// The calls the message was observed from; empty if it means the same from any call.
+    ops: &'static [&'static str],
+    /// A substring of the message.
+    pattern: &'static str,
+    kind: BackendErrorKind,
+}
+
+/// The calls that build a circuit from a constraint system.
+const CIRCUIT_OPS: &[&str] = &[
+    "acir_get_circuit_sizes",
+    "acir_init_proving_key",
+    "acir_create_proof",
+];
+
+/// Checked in order; the first matching rule decides the kind.
+const RULES: &[Rule] = &[
+    Rule {
+        ops: &[],
+        // Sic.
+        pattern: "You need to initalize the global CRS",
+        kind: BackendErrorKind::CrsTooSmall,
+    },
+    Rule {
+        ops: CIRCUIT_OPS,
+        pattern: "std::bad_alloc",
+        kind: BackendErrorKind::CircuitTooLarge,
+    },
+    Rule {
+        // A proof shorter than its fixed part makes the verifier size a huge public input vector.
+        ops: &["acir_verify_proof"],
+        pattern: "std::bad_alloc",
+        kind: BackendErrorKind::MalformedInput,
+    },
+    Rule {
+        ops: &[],
+        pattern: "is not a valid point",
+        kind: BackendErrorKind::MalformedInput,
+    },
+    Rule {
+        ops: &[],
+        pattern: "Serialized transcript does not contain the required number of bytes",
+        kind: BackendErrorKind::MalformedInput,
+    },
+    // Deserialization of the bincode-encoded ACIR buffers.
+    Rule {
+        ops: &[],
+        pattern: "Input is not large enough",
+        kind: BackendErrorKind::MalformedInput,
+    },
+    Rule {
+        ops: &[],
+        pattern: "Some input bytes were not read",
+        kind: BackendErrorKind::MalformedInput,
+    },
+    Rule {
+        ops: &[],
+        pattern: "Unknown variant index for enum",
+        kind: BackendErrorKind::MalformedInput,
+    },
+    Rule {
+        ops: &[],
+        pattern: "Length is too large",
+        kind: BackendErrorKind::MalformedInput,
+    },
+    Rule {
+        ops: &[],
+        pattern: "Invalid boolean value",
+        kind: BackendErrorKind::MalformedInput,
+    },
+    Rule {
+        ops: &[],
+        pattern: "Invalid UTF8 string",
+        kind: BackendErrorKind::MalformedInput,
+    },
+];
+
+/// Classifies the message barretenberg reported from the call `op`.
+pub fn classify(op: &str, message: &str) -> BackendErrorKind {
+    RULES
+        .iter()
+        .find(|rule| {
+            (rule.ops.is_empty() || rule.ops.contains(&op)) && message.contains(rule.pattern)
+        })
+        .map_or_else(
+            || BackendErrorKind::Unknown(message.to_string()),
+            |rule| rule.kind.clone(),
+        )
+}
+
+impl BackendError {
+    /// Classifies the error, see [`BackendErrorKind`].
+    ///
+    /// Backend messages are matched against a table of known failures. Inputs rejected on the
+    /// Rust side are [`BackendErrorKind::MalformedInput`], and a CRS found too small before the
+    /// call is [`BackendErrorKind::CrsTooSmall`]; the remaining errors are not reported
+    /// by the backend and are [`BackendErrorKind::Unknown`] with their display text.
+    pub fn kind(&self) -> BackendErrorKind {
+        match self {
+            BackendError::Backend { op, message, .. } => classify(op.name, message),
+            BackendError::MalformedInput { .. } => BackendErrorKind::MalformedInput,
+            BackendError::CrsTooSmall { .. } => BackendErrorKind::CrsTooSmall,
+            other => BackendErrorKind::Unknown(other.to_string()),
+        }
+    }
+}
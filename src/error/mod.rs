@@ -0,0 +1,414 @@
+use std::ffi::{c_char, CStr};
+use std::fmt;
+
+pub mod kind;
+#[cfg(test)]
+pub mod test;
+
+pub use kind::BackendErrorKind;
+
+use crate::acir_proofs::proof::ProofMode;
+
+/// The barretenberg call an error came from, together with the context it was invoked with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Operation {
+    /// Name of the C function, e.g. `acir_create_proof`.
+    pub name: &'static str,
+    /// Lengths in bytes of the input buffers, labelled as in the error message (`cs`, `witness`).
+    pub inputs: Vec<(&'static str, usize)>,
+    /// The circuit size hint of the composer the call was made on, if any.
+    pub size_hint: Option<u32>,
+}
+
+impl Operation {
+    pub(crate) fn new(name: &'static str) -> Self {
+        Operation {
+            name,
+            inputs: Vec::new(),
+            size_hint: None,
+        }
+    }
+
+    /// Records the length of the input buffer `label`.
+    pub(crate) fn input(mut self, label: &'static str, len: usize) -> Self {
+        self.inputs.push((label, len));
+        self
+    }
+
+    pub(crate) fn size_hint(mut self, size_hint: u32) -> Self {
+        self.size_hint = Some(size_hint);
+        self
+    }
+
+    #[cfg(feature = "tracing")]
+    pub(crate) fn input_lens(&self) -> InputLens<'_> {
+        InputLens(self)
+    }
+
+    /// Writes the recorded context as ` (cs=18432 bytes, witness=2048 bytes, size_hint=0)`, or
+    /// nothing if there is none.
+    fn fmt_context(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut details: Vec<String> = self
+            .inputs
+            .iter()
+            .map(|(label, len)| format!("{label}={len} bytes"))
+            .collect();
+        if let Some(size_hint) = self.size_hint {
+            details.push(format!("size_hint={size_hint}"));
+        }
+        if details.is_empty() {
+            Ok(())
+        } else {
+            write!(f, " ({})", details.join(", "))
+        }
+    }
+}
+
+/// Displays the recorded input lengths as `cs:18432,witness:2048`.
+#[cfg(feature = "tracing")]
+pub(crate) struct InputLens<'a>(&'a Operation);
+
+#[cfg(feature = "tracing")]
+impl fmt::Display for InputLens<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (label, len)) in self.0.inputs.iter().enumerate() {
+            if i > 0 {
+                f.write_str(",")?;
+            }
+            write!(f, "{label}:{len}")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Operation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name)?;
+        self.fmt_context(f)
+    }
+}
+
+/// Errors surfaced by the safe wrappers around the barretenberg C API.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BackendError {
+    /// The backend reported success but left an output pointer null.
+    FfiNull { op: Operation },
+    /// The backend threw a C++ exception; `message` is its `what()` text, decoded lossily, and
+    /// `raw_message` holds the exact bytes in case they are not valid UTF-8.
+    Backend {
+        op: Operation,
+        message: String,
+        raw_message: Vec<u8>,
+    },
+    /// The backend returned an output string that is not valid UTF-8.
+    InvalidUtf8 { op: Operation },
+    /// The backend returned an output buffer whose length prefix claims more than `max` bytes, more
+    /// than the call can legitimately produce.
+    BufferTooLarge {
+        op: Operation,
+        len: usize,
+        max: usize,
+    },
+    /// The backend returned field elements in an output buffer whose `len` bytes of elements are
+    /// not a multiple of 32.
+    Misaligned { op: Operation, len: usize },
+    /// The backend returned an output buffer whose layout does not match what it should hold.
+    MalformedOutput { op: Operation, reason: String },
+    /// The input was rejected on the Rust side because its layout is inconsistent with its length,
+    /// and the backend could read past its end.
+    MalformedInput { op: Operation, reason: String },
+    /// The size hint is zero, or exceeds `max` once rounded up to a power of two.
+    InvalidSizeHint { op: Operation, got: u32, max: u32 },
+    /// The call needs a proving key, or a verification key derived from one, and none was
+    /// initialized.
+    ProvingKeyNotInitialized { op: Operation },
+    /// The call needs a verification key and none was computed or loaded.
+    VerificationKeyNotInitialized { op: Operation },
+    /// The composer already built a circuit, which the call would append to.
+    CircuitAlreadyBuilt { op: Operation },
+    /// The call proves a witness of a circuit set beforehand, and none was.
+    ConstraintSystemNotSet { op: Operation },
+    /// The backend produced a result that contradicts a known answer, see [`crate::self_check`].
+    SelfCheckFailed { op: Operation, reason: String },
+    /// The process-wide CRS holds `num_points` G1 points, fewer than the `needed` the call would
+    /// use; the backend aborts the process rather than reporting it.
+    CrsTooSmall {
+        op: Operation,
+        num_points: u32,
+        needed: u32,
+    },
+    /// The proof was created in one mode and was to be verified in another, which cannot succeed.
+    ProofModeMismatch {
+        op: Operation,
+        proof: ProofMode,
+        requested: ProofMode,
+    },
+}
+
+impl BackendError {
+    /// The barretenberg call that failed.
+    pub fn op(&self) -> &Operation {
+        match self {
+            BackendError::FfiNull { op }
+            | BackendError::Backend { op, .. }
+            | BackendError::InvalidUtf8 { op }
+            | BackendError::BufferTooLarge { op, .. }
+            | BackendError::Misaligned { op, .. }
+            | BackendError::MalformedOutput { op, .. }
+            | BackendError::MalformedInput { op, .. }
+            | BackendError::InvalidSizeHint { op, .. }
+            | BackendError::ProvingKeyNotInitialized { op }
+            | BackendError::VerificationKeyNotInitialized { op }
+            | BackendError::CircuitAlreadyBuilt { op }
+            | BackendError::ConstraintSystemNotSet { op }
+            | BackendError::SelfCheckFailed { op, .. }
+            | BackendError::CrsTooSmall { op, .. }
+            | BackendError::ProofModeMismatch { op, .. } => op,
+        }
+    }
+}
+
+impl fmt::Display for BackendError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BackendError::FfiNull { op } => {
+                write!(f, "{} returned a null pointer", op.name)?;
+                op.fmt_context(f)
+            }
+            BackendError::Backend { op, message, .. } => {
+                write!(f, "{} failed", op.name)?;
+                op.fmt_context(f)?;
+                write!(f, ": {message}")
+            }
+            BackendError::InvalidUtf8 { op } => {
+                write!(f, "{} returned a string that is not valid UTF-8", op.name)?;
+                op.fmt_context(f)
+            }
+            BackendError::BufferTooLarge { op, len, max } => {
+                write!(
+                    f,
+                    "{} returned a {len}-byte buffer, over the limit of {max} bytes",
+                    op.name
+                )?;
+                op.fmt_context(f)
+            }
+            BackendError::Misaligned { op, len } => {
+                write!(
+                    f,
+                    "{} returned {len} bytes of field elements, which is not a multiple of 32",
+                    op.name
+                )?;
+                op.fmt_context(f)
+            }
+            BackendError::MalformedOutput { op, reason } => {
+                write!(f, "{} returned a malformed buffer", op.name)?;
+                op.fmt_context(f)?;
+                write!(f, ": {reason}")
+            }
+            BackendError::MalformedInput { op, reason } => {
+                write!(f, "{} rejected its input", op.name)?;
+                op.fmt_context(f)?;
+                write!(f, ": {reason}")
+            }
+            BackendError::InvalidSizeHint { op, got, max } => write!(
+                f,
+                "{} rejected size hint {got}: it must be between 1 and {max} once rounded up to \
+                 a power of two",
+                op.name
+            ),
+            BackendError::ProvingKeyNotInitialized { op } => {
+                write!(f, "{} requires an initialized proving key", op.name)?;
+                op.fmt_context(f)
+            }
+            BackendError::VerificationKeyNotInitialized { op } => {
+                write!(f, "{} requires an initialized verification key", op.name)?;
+                op.fmt_context(f)
+            }
+            BackendError::CircuitAlreadyBuilt { op } => {
+                write!(f, "{} requires a composer that has not built a circuit yet", op.name)?;
+                op.fmt_context(f)
+            }
+            BackendError::ConstraintSystemNotSet { op } => {
+                write!(
+                    f,
+                    "{} requires a constraint system set with set_constraint_system",
+                    op.name
+                )?;
+                op.fmt_context(f)
+            }
+            BackendError::SelfCheckFailed { op, reason } => {
+                write!(f, "self-check of {} failed", op.name)?;
+                op.fmt_context(f)?;
+                write!(f, ": {reason}")
+            }
+            BackendError::CrsTooSmall {
+                op,
+                num_points,
+                needed,
+            } => {
+                write!(
+                    f,
+                    "{} needs a CRS of {needed} points, it holds {num_points}",
+                    op.name
+                )?;
+                op.fmt_context(f)
+            }
+            BackendError::ProofModeMismatch {
+                op,
+                proof,
+                requested,
+            } => {
+                write!(f, "{} cannot verify a {proof} proof as {requested}", op.name)?;
+                op.fmt_context(f)
+            }
+        }
+    }
+}
+
+impl std::error::Error for BackendError {}
+
+/// For callers that only want the message, e.g. to keep a `Result<_, String>` signature.
+impl From<BackendError> for String {
+    fn from(err: BackendError) -> Self {
+        err.to_string()
+    }
+}
+
+/// Copies the raw bytes of the error message returned by a barretenberg call.
+///
+/// Every entry point does `return e.what();` from inside the `catch` block, and the exception is
+/// destroyed when that handler exits. For the `std::runtime_error` that `throw_or_abort` throws,
+/// the message lives in the exception's own reference-counted allocation, so the pointer already
+/// dangles when it reaches Rust: the bytes stay readable only until the allocator hands the
+/// memory out again. Callers must therefore copy them straight after the call returns, before
+/// anything else allocates or calls into the backend. The message must not be freed either, as
+/// the backend already released it and owns no other copy.
+///
+/// # Safety
+/// `error_msg_ptr` must be non-null and point to a nul-terminated string whose memory has not
+/// been reused since the call that returned it.
+pub(crate) unsafe fn take_error(error_msg_ptr: *const c_char) -> Vec<u8> {
+    CStr::from_ptr(error_msg_ptr).to_bytes().to_vec()
+}
+
+/// Converts the error message pointer returned by the barretenberg call `op` into a `Result`.
+///
+/// With the `tracing` feature enabled every failure is also emitted as a `tracing::error!` event,
+/// and with the `log` feature as a `log::error!` record.
+pub(crate) fn check_error(
+    op: &Operation,
+    error_msg_ptr: *const c_char,
+) -> Result<(), BackendError> {
+    if error_msg_ptr.is_null() {
+        return Ok(());
+    }
+    let raw_message = unsafe { take_error(error_msg_ptr) };
+    let err = BackendError::Backend {
+        op: op.clone(),
+        message: String::from_utf8_lossy(&raw_message).into_owned(),
+        raw_message,
+    };
+    #[cfg(feature = "tracing")]
+    tracing::error!(op = err.op().name, "{}", err);
+    #[cfg(feature = "log")]
+    log::error!("{}", err);
+    Err(err)
+}
+
+/// Serializes every call into the backend.
+#[cfg(not(feature = "parking_lot"))]
+static BACKEND_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+#[cfg(feature = "parking_lot")]
+static BACKEND_LOCK: parking_lot::Mutex<()> = parking_lot::const_mutex(());
+
+/// Waits until no other thread is inside a backend call.
+#[cfg(not(feature = "parking_lot"))]
+pub(crate) fn lock_backend() -> std::sync::MutexGuard<'static, ()> {
+    // Only the backend call and the copy of its error message run under the lock, and neither
+    // unwinds, so poisoning never leaves anything half-done.
+    BACKEND_LOCK
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+}
+
+/// Waits until no other thread is inside a backend call.
+#[cfg(feature = "parking_lot")]
+pub(crate) fn lock_backend() -> parking_lot::MutexGuard<'static, ()> {
+    BACKEND_LOCK.lock()
+}
+
+/// Calls the barretenberg function `$func` and converts its returned error pointer into a
+/// `Result<(), BackendError>` tagged with the [`Operation`] `$op`.
+///
+/// Every fallible entry point is invoked through this macro, so each call site only has to
+/// justify that its arguments are valid for the duration of the call. Temporaries created in
+/// the argument list, such as `InputBuffer::new(..).serialize().as_ptr()`, live until the end of
+/// the enclosing statement and therefore outlive the call. barretenberg catches every C++
+/// exception at the boundary and reports it through the returned pointer, so none unwinds into
+/// Rust.
+///
+/// The entry points without an error pointer, such as the hash and curve functions, catch nothing:
+/// an exception escaping one finds no handler in the Rust frames above it, and the C++ runtime
+/// terminates the process. No barretenberg function takes a callback, so Rust code never runs
+/// inside a call and a panic cannot unwind through C++ frames.
+///
+/// The call holds the lock returned by [`lock_backend`] until the error message, if any, has been
+/// copied, so calls run one at a time even on composers owned by different threads. The entry
+/// points without an error pointer are called directly, and take the same lock themselves.
+/// barretenberg keeps mutable state shared by all of them: the CRS that `srs_init_srs` replaces,
+/// and lookup tables such as the plookup multi-tables and the generator tables in
+/// `crypto/generators/generator_data.cpp`, which the first caller to use them fills in without
+/// synchronization, whether that is a prover or a Pedersen hash. Threads therefore take turns
+/// rather than running in parallel; separate processes do not share the lock. With the
+/// `parking_lot` feature the lock is a `parking_lot::Mutex`.
+///
+/// Prefixing the operation with `$composer;` routes the result through the composer's own
+/// `check_error`, which also records it for `AcirComposer::last_backend_error`. Writing
+/// `$composer; timed $op` instead returns the result together with how long the backend call
+/// took, measured once the lock is held.
+///
+/// With the `tracing` feature enabled the call runs inside a `DEBUG` span named after `$func`,
+/// with the input lengths and size hint of `$op` and the time spent in the call, `duration_ms`,
+/// as fields.
+macro_rules! ffi_call {
+    (@call $check:expr, $op:expr, $func:ident($($arg:expr),*)) => {{
+        let backend = $crate::error::lock_backend();
+        #[cfg(feature = "tracing")]
+        let span = tracing::debug_span!(
+            stringify!($func),
+            inputs = %$op.input_lens(),
+            size_hint = $op.size_hint,
+            duration_ms = tracing::field::Empty,
+        );
+        #[cfg(feature = "tracing")]
+        let entered = span.enter();
+        // Timed only once the lock is held, so waiting for other calls is not counted.
+        let start = std::time::Instant::now();
+        let ret = unsafe { $func($($arg),*) };
+        let duration = start.elapsed();
+        // The error message is already freed, so it is copied before another call can reuse its
+        // memory, see `take_error`.
+        let result = $check(&$op, ret);
+        #[cfg(feature = "tracing")]
+        {
+            span.record("duration_ms", duration.as_secs_f64() * 1000.0);
+            drop(entered);
+        }
+        drop(backend);
+        (result, duration)
+    }};
+    ($composer:ident; timed $op:expr, $func:ident($($arg:expr),* $(,)?)) => {
+        $crate::error::ffi_call!(
+            @call |op, ret| $composer.check_error(op, ret),
+            $op,
+            $func($($arg),*)
+        )
+    };
+    ($composer:ident; $op:expr, $func:ident($($arg:expr),* $(,)?)) => {
+        $crate::error::ffi_call!($composer; timed $op, $func($($arg),*)).0
+    };
+    ($op:expr, $func:ident($($arg:expr),* $(,)?)) => {
+        $crate::error::ffi_call!(@call $crate::error::check_error, $op, $func($($arg),*)).0
+    };
+}
+pub(crate) use ffi_call;
@@ -0,0 +1,176 @@
+use std::ffi::CString;
+
+use super::kind::classify;
+use super::{check_error, BackendError, BackendErrorKind, Operation};
+
+#[test]
+fn test_display_lists_input_sizes() {
+    let err = BackendError::Backend {
+        op: Operation::new("acir_create_proof")
+            .input("cs", 18432)
+            .input("witness", 2048),
+        message: "bad witness".to_string(),
+        raw_message: b"bad witness".to_vec(),
+    };
+    assert_eq!(
+        err.to_string(),
+        "acir_create_proof failed (cs=18432 bytes, witness=2048 bytes): bad witness"
+    );
+}
+
+#[test]
+fn test_display_without_context() {
+    let err = BackendError::FfiNull {
+        op: Operation::new("acir_get_verification_key"),
+    };
+    assert_eq!(
+        err.to_string(),
+        "acir_get_verification_key returned a null pointer"
+    );
+
+    let err = BackendError::InvalidUtf8 {
+        op: Operation::new("acir_get_solidity_verifier").size_hint(0),
+    };
+    assert_eq!(
+        err.to_string(),
+        "acir_get_solidity_verifier returned a string that is not valid UTF-8 (size_hint=0)"
+    );
+}
+
+#[test]
+fn test_display_buffer_too_large() {
+    let err = BackendError::BufferTooLarge {
+        op: Operation::new("acir_get_verification_key"),
+        len: 4_294_967_295,
+        max: 1 << 20,
+    };
+    assert_eq!(
+        err.to_string(),
+        "acir_get_verification_key returned a 4294967295-byte buffer, over the limit of 1048576 bytes"
+    );
+}
+
+#[test]
+fn test_display_misaligned() {
+    let err = BackendError::Misaligned {
+        op: Operation::new("acir_serialize_proof_into_fields"),
+        len: 100,
+    };
+    assert_eq!(
+        err.to_string(),
+        "acir_serialize_proof_into_fields returned 100 bytes of field elements, which is not a \
+         multiple of 32"
+    );
+}
+
+#[test]
+fn test_converts_into_string_and_boxed_error() {
+    fn as_string() -> Result<(), String> {
+        Err(BackendError::FfiNull {
+            op: Operation::new("acir_get_verification_key"),
+        })?
+    }
+    fn as_boxed() -> Result<(), Box<dyn std::error::Error>> {
+        Err(BackendError::FfiNull {
+            op: Operation::new("acir_get_verification_key"),
+        })?
+    }
+
+    let expected = "acir_get_verification_key returned a null pointer";
+    assert_eq!(as_string().unwrap_err(), expected);
+    assert_eq!(as_boxed().unwrap_err().to_string(), expected);
+}
+
+#[test]
+fn test_check_error_keeps_non_utf8_bytes() {
+    let raw = b"expected 4 bytes, got \xff\xfe".to_vec();
+    let c_message = CString::new(raw.clone()).unwrap();
+
+    match check_error(&Operation::new("acir_create_proof"), c_message.as_ptr()) {
+        Err(BackendError::Backend {
+            message,
+            raw_message,
+            ..
+        }) => {
+            assert_eq!(message, "expected 4 bytes, got \u{fffd}\u{fffd}");
+            assert_eq!(raw_message, raw);
+        }
+        other => panic!("expected a backend error, got {:?}", other),
+    }
+}
+
+/// Messages captured from the backend, with the call that reported them and their expected kind.
+const CORPUS: &[(&str, &str, BackendErrorKind)] = &[
+    (
+        "acir_init_proving_key",
+        "You need to initalize the global CRS with a call to init_crs_factory(...)!",
+        BackendErrorKind::CrsTooSmall,
+    ),
+    (
+        "acir_init_proving_key",
+        "std::bad_alloc",
+        BackendErrorKind::CircuitTooLarge,
+    ),
+    (
+        "acir_verify_proof",
+        "std::bad_alloc",
+        BackendErrorKind::MalformedInput,
+    ),
+    (
+        "acir_verify_proof",
+        "polynomial commitment to witness is not a valid point.",
+        BackendErrorKind::MalformedInput,
+    ),
+    (
+        "acir_serialize_proof_into_fields",
+        "Serialized transcript does not contain the required number of bytes: 2176 != 100",
+        BackendErrorKind::MalformedInput,
+    ),
+    (
+        "acir_get_circuit_sizes",
+        "Input is not large enough",
+        BackendErrorKind::MalformedInput,
+    ),
+    (
+        "acir_init_proving_key",
+        "Some input bytes were not read",
+        BackendErrorKind::MalformedInput,
+    ),
+    (
+        "acir_get_circuit_sizes",
+        "Unknown variant index for enum",
+        BackendErrorKind::MalformedInput,
+    ),
+];
+
+#[test]
+fn test_classify_known_messages() {
+    for (op, message, kind) in CORPUS {
+        assert_eq!(&classify(op, message), kind, "{op}: {message}");
+    }
+}
+
+#[test]
+fn test_classify_unknown_message() {
+    assert_eq!(
+        classify("acir_create_proof", "something new"),
+        BackendErrorKind::Unknown("something new".to_string())
+    );
+}
+
+#[test]
+fn test_kind_of_rust_side_errors() {
+    let op = Operation::new("acir_create_proof");
+    let err = BackendError::MalformedInput {
+        op: op.clone(),
+        reason: "empty witness".to_string(),
+    };
+    assert_eq!(err.kind(), BackendErrorKind::MalformedInput);
+
+    let err = BackendError::Backend {
+        op,
+        message: "std::bad_alloc".to_string(),
+        raw_message: b"std::bad_alloc".to_vec(),
+    };
+    assert_eq!(err.kind(), BackendErrorKind::CircuitTooLarge);
+}
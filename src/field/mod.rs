@@ -0,0 +1,108 @@
+use std::fmt;
+
+#[cfg(test)]
+pub mod test;
+
+/// The order of the BN254 scalar field, as big-endian bytes.
+pub const MODULUS: [u8; 32] = [
+    0x30, 0x64, 0x4e, 0x72, 0xe1, 0x31, 0xa0, 0x29, 0xb8, 0x50, 0x45, 0xb6, 0x81, 0x81, 0x58, 0x5d,
+    0x28, 0x33, 0xe8, 0x48, 0x79, 0xb9, 0x70, 0x91, 0x43, 0xe1, 0xf5, 0x93, 0xf0, 0x00, 0x00, 0x01,
+];
+
+/// An element of the BN254 scalar field, the field barretenberg's circuits and hashes work over.
+///
+/// It holds the canonical big-endian encoding, which is also how barretenberg serializes field
+/// elements, so it can be passed to the backend as is.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Fr([u8; 32]);
+
+impl Fr {
+    /// Parses a canonical big-endian encoding, rejecting values not smaller than [`MODULUS`].
+    pub fn from_be_bytes(bytes: [u8; 32]) -> Result<Self, FieldError> {
+        // Big-endian byte arrays compare like the numbers they encode.
+        if bytes < MODULUS {
+            Ok(Fr(bytes))
+        } else {
+            Err(FieldError::NotCanonical)
+        }
+    }
+
+    /// Parses a canonical little-endian encoding, rejecting values not smaller than [`MODULUS`].
+    pub fn from_le_bytes(mut bytes: [u8; 32]) -> Result<Self, FieldError> {
+        bytes.reverse();
+        Self::from_be_bytes(bytes)
+    }
+
+    /// Interprets `bytes` as a big-endian integer of any length and reduces it modulo
+    /// [`MODULUS`].
+    pub fn from_be_bytes_mod_order(bytes: &[u8]) -> Self {
+        let mut acc = [0u8; 32];
+        for byte in bytes {
+            for bit in (0..8).rev() {
+                // `acc` stays below the 254-bit modulus, so doubling it cannot overflow.
+                let mut carry = (byte >> bit) & 1;
+                for limb in acc.iter_mut().rev() {
+                    let next = *limb >> 7;
+                    *limb = (*limb << 1) | carry;
+                    carry = next;
+                }
+                if acc >= MODULUS {
+                    sub_modulus(&mut acc);
+                }
+            }
+        }
+        Fr(acc)
+    }
+
+    pub fn from_u64(value: u64) -> Self {
+        let mut bytes = [0u8; 32];
+        bytes[24..].copy_from_slice(&value.to_be_bytes());
+        Fr(bytes)
+    }
+
+    pub fn to_be_bytes(self) -> [u8; 32] {
+        self.0
+    }
+
+    pub fn to_le_bytes(self) -> [u8; 32] {
+        let mut bytes = self.0;
+        bytes.reverse();
+        bytes
+    }
+}
+
+impl From<u64> for Fr {
+    fn from(value: u64) -> Self {
+        Fr::from_u64(value)
+    }
+}
+
+/// Subtracts [`MODULUS`] from `acc`, which must not be smaller than it.
+fn sub_modulus(acc: &mut [u8; 32]) {
+    let mut borrow = 0;
+    for (limb, modulus) in acc.iter_mut().zip(MODULUS).rev() {
+        let (diff, underflow) = limb.overflowing_sub(modulus);
+        let (diff, underflow_borrow) = diff.overflowing_sub(borrow);
+        *limb = diff;
+        borrow = u8::from(underflow || underflow_borrow);
+    }
+}
+
+/// Errors from parsing a [`Fr`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldError {
+    /// The value is not smaller than [`MODULUS`].
+    NotCanonical,
+}
+
+impl fmt::Display for FieldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FieldError::NotCanonical => {
+                f.write_str("value is not smaller than the BN254 scalar field order")
+            }
+        }
+    }
+}
+
+impl std::error::Error for FieldError {}
@@ -0,0 +1,52 @@
+use super::{FieldError, Fr, MODULUS};
+
+fn modulus_plus(value: u8) -> [u8; 32] {
+    let mut bytes = MODULUS;
+    bytes[31] += value;
+    bytes
+}
+
+#[test]
+fn test_from_u64_is_big_endian() {
+    let fr = Fr::from_u64(0x0102);
+    assert_eq!(fr.to_be_bytes()[30..], [0x01, 0x02]);
+    assert_eq!(fr.to_le_bytes()[..2], [0x02, 0x01]);
+    assert_eq!(Fr::from(0x0102), fr);
+}
+
+#[test]
+fn test_byte_order_round_trip() {
+    let fr = Fr::from_u64(u64::MAX);
+    assert_eq!(Fr::from_be_bytes(fr.to_be_bytes()), Ok(fr));
+    assert_eq!(Fr::from_le_bytes(fr.to_le_bytes()), Ok(fr));
+}
+
+#[test]
+fn test_rejects_non_canonical_encodings() {
+    let mut largest = MODULUS;
+    largest[31] -= 1;
+    assert!(Fr::from_be_bytes(largest).is_ok());
+
+    assert_eq!(Fr::from_be_bytes(MODULUS), Err(FieldError::NotCanonical));
+    assert_eq!(Fr::from_be_bytes([0xff; 32]), Err(FieldError::NotCanonical));
+    let mut modulus_le = MODULUS;
+    modulus_le.reverse();
+    assert_eq!(Fr::from_le_bytes(modulus_le), Err(FieldError::NotCanonical));
+}
+
+#[test]
+fn test_from_be_bytes_mod_order() {
+    assert_eq!(Fr::from_be_bytes_mod_order(&MODULUS), Fr::default());
+    assert_eq!(
+        Fr::from_be_bytes_mod_order(&modulus_plus(5)),
+        Fr::from_u64(5)
+    );
+    assert_eq!(
+        Fr::from_be_bytes_mod_order(&[0x01, 0x02]),
+        Fr::from_u64(0x0102)
+    );
+    assert_eq!(
+        hex::encode(Fr::from_be_bytes_mod_order(&[0xff; 32]).to_be_bytes()),
+        "0e0a77c19a07df2f666ea36f7879462e36fc76959f60cd29ac96341c4ffffffa"
+    );
+}
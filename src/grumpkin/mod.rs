@@ -0,0 +1,117 @@
+use crate::{error::lock_backend, pedersen::POINT_AT_INFINITY_X};
+#[cfg(feature = "grumpkin-arithmetic")]
+use num_bigint::BigUint;
+
+#[cfg(test)]
+pub mod test;
+
+extern "C" {
+    // Exported by `ecc/curves/grumpkin/c_bind.cpp`, which has no header for bindgen to read.
+    fn ecc_grumpkin__mul(point_buf: *const u8, scalar_buf: *const u8, result: *mut u8);
+}
+
+/// The generator of the Grumpkin group, `(1, sqrt(-16))`.
+pub const GENERATOR: ([u8; 32], [u8; 32]) = (
+    [
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x01,
+    ],
+    [
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02, 0xcf, 0x13, 0x5e, 0x75, 0x06, 0xa4, 0x5d,
+        0x63, 0x2d, 0x27, 0x0d, 0x45, 0xf1, 0x18, 0x12, 0x94, 0x83, 0x3f, 0xc4, 0x8d, 0x82, 0x3f,
+        0x27, 0x2c,
+    ],
+);
+
+/// The point at infinity as barretenberg serializes it: `x` is the flag it sets in the top bit of
+/// the Montgomery form of `x`, converted out of that form, and `y` is zero. It is not on the curve,
+/// so it cannot be mistaken for a finite point.
+pub const INFINITY: ([u8; 32], [u8; 32]) = (POINT_AT_INFINITY_X, [0u8; 32]);
+
+/// Multiplies the Grumpkin point `point` by `scalar`.
+///
+/// Points are affine `(x, y)` coordinates, each a big-endian element of the BN254 scalar field,
+/// with [`INFINITY`] for the point at infinity; `scalar` is a big-endian element of the Grumpkin
+/// scalar field, the BN254 base field. Coordinates of a point that is not on the curve give a
+/// meaningless result.
+pub fn mul(point: ([u8; 32], [u8; 32]), scalar: [u8; 32]) -> ([u8; 32], [u8; 32]) {
+    // barretenberg aborts converting the flagged `x` of the infinity encoding back to a point.
+    if point == INFINITY {
+        return INFINITY;
+    }
+    let mut point_buf = [0u8; 64];
+    point_buf[..32].copy_from_slice(&point.0);
+    point_buf[32..].copy_from_slice(&point.1);
+    let mut output = [0u8; 64];
+    let _backend = lock_backend();
+    unsafe { ecc_grumpkin__mul(point_buf.as_ptr(), scalar.as_ptr(), output.as_mut_ptr()) };
+    let (x, y) = output.split_at(32);
+    (x.try_into().unwrap(), y.try_into().unwrap())
+}
+
+/// Adds two Grumpkin points, encoded as for [`mul`].
+///
+/// barretenberg exports no addition, so this is computed in Rust with the affine formulas, on
+/// arbitrary-precision integers. It is not constant-time: the inversions and the branches depend
+/// on the points, so it must not be used on secret ones.
+#[cfg(feature = "grumpkin-arithmetic")]
+pub fn add(a: ([u8; 32], [u8; 32]), b: ([u8; 32], [u8; 32])) -> ([u8; 32], [u8; 32]) {
+    if a == INFINITY {
+        return b;
+    }
+    if b == INFINITY {
+        return a;
+    }
+    let p = BigUint::from_bytes_be(&crate::field::MODULUS);
+    let (x1, y1) = (to_field(&a.0, &p), to_field(&a.1, &p));
+    let (x2, y2) = (to_field(&b.0, &p), to_field(&b.1, &p));
+    let sub = |l: &BigUint, r: &BigUint| (l + &p - r) % &p;
+    // Fermat inversion: p is prime.
+    let inv = |v: &BigUint| v.modpow(&(&p - 2u32), &p);
+
+    let slope = if x1 == x2 {
+        if (&y1 + &y2) % &p == BigUint::default() {
+            // `b` is `-a`, which includes doubling a point with `y = 0`.
+            return INFINITY;
+        }
+        BigUint::from(3u32) * &x1 * &x1 * inv(&(BigUint::from(2u32) * &y1)) % &p
+    } else {
+        sub(&y2, &y1) * inv(&sub(&x2, &x1)) % &p
+    };
+    let x3 = sub(&(&slope * &slope % &p), &((&x1 + &x2) % &p));
+    let y3 = sub(&(slope * sub(&x1, &x3) % &p), &y1);
+    (from_field(&x3), from_field(&y3))
+}
+
+/// Whether `point` satisfies the curve equation, or is [`INFINITY`]. Like [`add`], it is not
+/// constant-time.
+#[cfg(feature = "grumpkin-arithmetic")]
+pub fn is_on_curve(point: ([u8; 32], [u8; 32])) -> bool {
+    if point == INFINITY {
+        return true;
+    }
+    let p = BigUint::from_bytes_be(&crate::field::MODULUS);
+    let (x, y) = (
+        BigUint::from_bytes_be(&point.0),
+        BigUint::from_bytes_be(&point.1),
+    );
+    if x >= p || y >= p {
+        return false;
+    }
+    // y^2 = x^3 - 17
+    (&y * &y + 17u32) % &p == (&x * &x * &x) % &p
+}
+
+#[cfg(feature = "grumpkin-arithmetic")]
+fn to_field(bytes: &[u8; 32], p: &BigUint) -> BigUint {
+    BigUint::from_bytes_be(bytes) % p
+}
+
+#[cfg(feature = "grumpkin-arithmetic")]
+fn from_field(value: &BigUint) -> [u8; 32] {
+    let bytes = value.to_bytes_be();
+    let mut out = [0u8; 32];
+    out[32 - bytes.len()..].copy_from_slice(&bytes);
+    out
+}
@@ -0,0 +1,89 @@
+use super::{ecc_grumpkin__mul, mul, GENERATOR, INFINITY};
+#[cfg(feature = "grumpkin-arithmetic")]
+use super::{add, is_on_curve};
+use crate::dealloc::test::{assert_aborted, is_child, run_in_child};
+
+/// The order of the Grumpkin group, which is the BN254 base field modulus.
+const ORDER: [u8; 32] = [
+    0x30, 0x64, 0x4e, 0x72, 0xe1, 0x31, 0xa0, 0x29, 0xb8, 0x50, 0x45, 0xb6, 0x81, 0x81, 0x58, 0x5d,
+    0x97, 0x81, 0x6a, 0x91, 0x68, 0x71, 0xca, 0x8d, 0x3c, 0x20, 0x8c, 0x16, 0xd8, 0x7c, 0xfd, 0x47,
+];
+
+fn scalar(value: u64) -> [u8; 32] {
+    let mut scalar = [0u8; 32];
+    scalar[24..].copy_from_slice(&value.to_be_bytes());
+    scalar
+}
+
+#[cfg(feature = "grumpkin-arithmetic")]
+#[test]
+fn test_generator_is_on_curve() {
+    assert!(is_on_curve(GENERATOR));
+    assert!(is_on_curve(INFINITY));
+    assert!(!is_on_curve((GENERATOR.0, [0u8; 32])));
+    assert_eq!(mul(GENERATOR, scalar(1)), GENERATOR);
+}
+
+#[cfg(feature = "grumpkin-arithmetic")]
+#[test]
+fn test_double_via_add_and_mul() {
+    let doubled = add(GENERATOR, GENERATOR);
+    assert_eq!(doubled, mul(GENERATOR, scalar(2)));
+    assert_eq!(
+        hex::encode(doubled.0),
+        "06ce1b0827aafa85ddeb49cdaa36306d19a74caa311e13d46d8bc688cdbffffe"
+    );
+    assert!(is_on_curve(doubled));
+}
+
+#[cfg(feature = "grumpkin-arithmetic")]
+#[test]
+fn test_add_matches_mul() {
+    let mut sum = INFINITY;
+    for k in 1..=10 {
+        sum = add(sum, GENERATOR);
+        assert_eq!(sum, mul(GENERATOR, scalar(k)), "{k}G");
+    }
+    let five = mul(GENERATOR, scalar(5));
+    let seven = mul(GENERATOR, scalar(7));
+    assert_eq!(add(five, seven), mul(GENERATOR, scalar(12)));
+    assert_eq!(add(five, seven), add(seven, five));
+}
+
+#[test]
+fn test_infinity() {
+    assert_eq!(mul(GENERATOR, scalar(0)), INFINITY);
+    assert_eq!(mul(GENERATOR, ORDER), INFINITY);
+    assert_eq!(mul(INFINITY, scalar(3)), INFINITY);
+    assert_eq!(mul(GENERATOR, scalar(1)), GENERATOR);
+}
+
+#[cfg(feature = "grumpkin-arithmetic")]
+#[test]
+fn test_add_infinity() {
+    assert_eq!(add(GENERATOR, INFINITY), GENERATOR);
+    assert_eq!(add(INFINITY, GENERATOR), GENERATOR);
+
+    // G + (n - 1)G = nG
+    let mut order_minus_one = ORDER;
+    order_minus_one[31] -= 1;
+    let negated = mul(GENERATOR, order_minus_one);
+    assert_eq!(negated.0, GENERATOR.0);
+    assert_eq!(add(GENERATOR, negated), INFINITY);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_escaped_exception_aborts() {
+    if is_child() {
+        // The backend throws converting the infinity encoding back to a point, which `mul` avoids.
+        let mut point = [0u8; 64];
+        point[..32].copy_from_slice(&INFINITY.0);
+        let mut output = [0u8; 64];
+        unsafe { ecc_grumpkin__mul(point.as_ptr(), [1u8; 32].as_ptr(), output.as_mut_ptr()) };
+        unreachable!("the exception must not be swallowed");
+    }
+    assert_aborted(run_in_child(
+        "grumpkin::test::test_escaped_exception_aborts",
+    ));
+}
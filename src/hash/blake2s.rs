@@ -0,0 +1,11 @@
+use crate::{blake2s as bb_blake2s, buffer::InputBuffer, error::lock_backend};
+
+/// Hashes an arbitrary byte string with BLAKE2s-256.
+pub fn blake2s(input: &[u8]) -> [u8; 32] {
+    let mut output = [0u8; 32];
+    // The backend reads the input as a length-prefixed vector, not a raw pointer and length.
+    let input = InputBuffer::new(input).serialize();
+    let _backend = lock_backend();
+    unsafe { bb_blake2s(input.as_ptr(), output.as_mut_ptr()) };
+    output
+}
@@ -0,0 +1,24 @@
+use crate::{error::lock_backend, field::Fr};
+
+extern "C" {
+    // Exported by `crypto/blake3s/c_bind.cpp`, which has no header for bindgen to read.
+    fn blake3s_to_field(data: *const u8, length: usize, r: *mut u8);
+}
+
+/// The longest input barretenberg hashes correctly: its BLAKE3 only implements a single chunk.
+pub const MAX_BLAKE3_INPUT_LEN: usize = 1024;
+
+/// Hashes `input` with BLAKE3 and reduces the hash, read as a big-endian integer, into a field
+/// element, as barretenberg's `blake3s_to_field` does.
+///
+/// barretenberg exports no function that returns the raw hash. Returns `None` if `input` is
+/// longer than [`MAX_BLAKE3_INPUT_LEN`], which the backend would hash incorrectly.
+pub fn blake3_to_field(input: &[u8]) -> Option<Fr> {
+    if input.len() > MAX_BLAKE3_INPUT_LEN {
+        return None;
+    }
+    let mut output = [0u8; 32];
+    let _backend = lock_backend();
+    unsafe { blake3s_to_field(input.as_ptr(), input.len(), output.as_mut_ptr()) };
+    Some(Fr::from_be_bytes_mod_order(&output))
+}
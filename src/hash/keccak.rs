@@ -0,0 +1,14 @@
+use crate::{error::lock_backend, ethash_keccak256};
+
+/// Hashes an arbitrary byte string with the Ethereum flavour of Keccak-256, i.e. the original
+/// Keccak padding rather than the FIPS 202 SHA3-256 one, as used by the Solidity verifier.
+pub fn keccak256(input: &[u8]) -> [u8; 32] {
+    let _backend = lock_backend();
+    let digest = unsafe { ethash_keccak256(input.as_ptr(), input.len()) };
+    // The digest is returned as four native words whose in-memory bytes form the hash.
+    let mut output = [0u8; 32];
+    for (chunk, word) in output.chunks_exact_mut(8).zip(digest.word64s) {
+        chunk.copy_from_slice(&word.to_ne_bytes());
+    }
+    output
+}
@@ -0,0 +1,8 @@
+pub mod blake2s;
+#[cfg(feature = "blake3")]
+pub mod blake3;
+pub mod keccak;
+pub mod sha256;
+
+#[cfg(test)]
+pub mod test;
@@ -0,0 +1,14 @@
+use crate::error::lock_backend;
+
+extern "C" {
+    // Exported by `crypto/sha256/c_bind.cpp`, which has no header for bindgen to read.
+    fn sha256__hash(input: *const u8, length: usize, output: *mut u8);
+}
+
+/// Hashes an arbitrary byte string with SHA-256, as Noir's `std::hash::sha256` does.
+pub fn sha256(input: &[u8]) -> [u8; 32] {
+    let mut output = [0u8; 32];
+    let _backend = lock_backend();
+    unsafe { sha256__hash(input.as_ptr(), input.len(), output.as_mut_ptr()) };
+    output
+}
@@ -0,0 +1,85 @@
+use super::{
+    blake2s::blake2s,
+    keccak::keccak256,
+    sha256::sha256,
+};
+#[cfg(feature = "blake3")]
+use crate::field::Fr;
+
+#[test]
+fn test_blake2s_empty() {
+    assert_eq!(
+        hex::encode(blake2s(b"")),
+        "69217a3079908094e11121d042354a7c1f55b6482ca1a51e1b250dfd1ed0eef9"
+    );
+}
+
+#[test]
+fn test_blake2s_abc() {
+    assert_eq!(
+        hex::encode(blake2s(b"abc")),
+        "508c5e8c327c14e2e1a72ba34eeb452f37458b209ed63a294d999b4c86675982"
+    );
+}
+
+/// The input of the official BLAKE3 test vectors: the bytes 0, 1, …, 250 repeated.
+#[cfg(feature = "blake3")]
+fn blake3_vector_input(len: usize) -> Vec<u8> {
+    (0..len).map(|i| (i % 251) as u8).collect()
+}
+
+#[cfg(feature = "blake3")]
+#[test]
+fn test_blake3_to_field_vectors() {
+    use super::blake3::{blake3_to_field, MAX_BLAKE3_INPUT_LEN};
+
+    // The official hashes, which the backend reduces into the field.
+    let vectors = [
+        (0, "af1349b9f5f9a1a6a0404dea36dcc9499bcb25c9adc112b7cc9a93cae41f3262"),
+        (1, "2d3adedff11b61f14c886e35afa036736dcd87a74d27b5c1510225d0f592e213"),
+        (64, "4eed7141ea4a5cd4b788606bd23f46e212af9cacebacdc7d1f4c6dc7f2511b98"),
+        (65, "de1e5fa0be70df6d2be8fffd0e99ceaa8eb6e8c93a63f2d8d1c30ecb6b263dee"),
+        (1024, "42214739f095a406f3fc83deb889744ac00df831c10daa55189b5d121c855af7"),
+    ];
+    for (len, hash) in vectors {
+        let expected = Fr::from_be_bytes_mod_order(&hex::decode(hash).unwrap());
+        assert_eq!(blake3_to_field(&blake3_vector_input(len)), Some(expected), "{len}");
+    }
+    let abc = "6437b3ac38465133ffb63b75273a8db548c558465d79db03fd359c6cd5bd9d85";
+    let expected = Fr::from_be_bytes_mod_order(&hex::decode(abc).unwrap());
+    assert_eq!(blake3_to_field(b"abc"), Some(expected));
+    assert_eq!(blake3_to_field(&blake3_vector_input(MAX_BLAKE3_INPUT_LEN + 1)), None);
+}
+
+#[test]
+fn test_keccak256_empty_uses_keccak_padding() {
+    // SHA3-256 (0x06 padding) of the empty input would start with a7ffc6f8 instead.
+    assert_eq!(
+        hex::encode(keccak256(b"")),
+        "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"
+    );
+}
+
+#[test]
+fn test_keccak256_abc() {
+    assert_eq!(
+        hex::encode(keccak256(b"abc")),
+        "4e03657aea45a94fc7d47ba826c8d667c0d1e6e33a64a036ec44f58fa12d6c45"
+    );
+}
+
+#[test]
+fn test_sha256_empty() {
+    assert_eq!(
+        hex::encode(sha256(b"")),
+        "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+    );
+}
+
+#[test]
+fn test_sha256_abc() {
+    assert_eq!(
+        hex::encode(sha256(b"abc")),
+        "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+    );
+}
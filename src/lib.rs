@@ -0,0 +1,33 @@
+// Suppress the flurry of warnings caused by using "C" naming conventions
+#![allow(non_upper_case_globals)]
+#![allow(non_camel_case_types)]
+#![allow(non_snake_case)]
+
+// extern crate link_cplusplus;
+
+// This matches bindgen::Builder output
+include!(concat!(env!("OUT_DIR"), "/bindings.rs"));
+
+pub mod acir_proofs;
+pub mod aes;
+pub mod buffer;
+pub mod ct;
+pub mod dealloc;
+pub mod ecdsa;
+#[cfg(feature = "encoding")]
+pub mod encoding;
+pub mod error;
+pub mod field;
+pub mod grumpkin;
+pub mod hash;
+pub mod pedersen;
+pub mod schnorr;
+#[cfg(feature = "zeroize")]
+pub mod secret;
+pub mod self_check;
+pub mod srs;
+pub mod validation;
+pub mod version;
+
+pub use self_check::self_check;
+pub use version::barretenberg_version;
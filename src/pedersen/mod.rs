@@ -0,0 +1,52 @@
+use crate::{
+    buffer::serialize_fields, error::lock_backend, field::Fr, pedersen___plookup_commit,
+    pedersen_hash_multiple_with_hash_index,
+};
+
+#[cfg(test)]
+pub mod test;
+
+/// Hashes `inputs` with barretenberg's lookup-based Pedersen hash over the BN254 scalar field.
+pub fn pedersen_hash(inputs: &[Fr], hash_index: u32) -> Fr {
+    let mut output = [0u8; 32];
+    let _backend = lock_backend();
+    unsafe {
+        pedersen_hash_multiple_with_hash_index(
+            serialize_field_elements(inputs).as_slice().as_ptr(),
+            // The backend reads the hash index in network byte order.
+            &hash_index.to_be(),
+            output.as_mut_ptr(),
+        )
+    };
+    // The backend always returns a reduced field element.
+    Fr::from_be_bytes(output).unwrap()
+}
+
+/// The `x` coordinate barretenberg emits for the Grumpkin point at infinity.
+pub const POINT_AT_INFINITY_X: [u8; 32] = [
+    0x18, 0x32, 0x27, 0x39, 0x70, 0x98, 0xd0, 0x14, 0xdc, 0x28, 0x22, 0xdb, 0x40, 0xc0, 0xac, 0x2e,
+    0x94, 0x19, 0xf4, 0x24, 0x3c, 0xdc, 0xb8, 0x48, 0xa1, 0xf0, 0xfa, 0xc9, 0xf8, 0x00, 0x00, 0x01,
+];
+
+/// Commits to `inputs` with barretenberg's lookup-based Pedersen commitment, returning the
+/// resulting Grumpkin affine point as big-endian `(x, y)` coordinates.
+///
+/// An empty `inputs` slice commits to the point at infinity. barretenberg flags infinity by storing
+/// the raw field modulus in `x`, which serializes as [`POINT_AT_INFINITY_X`] with `y` set to zero.
+pub fn pedersen_commit(inputs: &[Fr]) -> ([u8; 32], [u8; 32]) {
+    let mut output = [0u8; 64];
+    let _backend = lock_backend();
+    unsafe {
+        pedersen___plookup_commit(
+            serialize_field_elements(inputs).as_slice().as_ptr(),
+            output.as_mut_ptr(),
+        )
+    };
+    let (x, y) = output.split_at(32);
+    (x.try_into().unwrap(), y.try_into().unwrap())
+}
+
+fn serialize_field_elements(inputs: &[Fr]) -> Vec<u8> {
+    let fields: Vec<[u8; 32]> = inputs.iter().map(|input| input.to_be_bytes()).collect();
+    serialize_fields(&fields)
+}
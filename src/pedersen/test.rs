@@ -0,0 +1,42 @@
+use super::{pedersen_commit, pedersen_hash, POINT_AT_INFINITY_X};
+use crate::field::Fr;
+
+fn field(value: u64) -> Fr {
+    Fr::from_u64(value)
+}
+
+#[test]
+fn test_pedersen_hash_two_fields() {
+    let hash = pedersen_hash(&[field(1), field(2)], 0);
+    assert_eq!(
+        hex::encode(hash.to_be_bytes()),
+        "1ecc3e451bab2412ce126fb8efbfecc95994dd6c2262a0fdf6a5520f09d46345"
+    );
+}
+
+#[test]
+fn test_pedersen_hash_index_separates_domains() {
+    let hash = pedersen_hash(&[field(1), field(2)], 5);
+    assert_eq!(
+        hex::encode(hash.to_be_bytes()),
+        "1ec1ffe390f1aa3dd9c4ca1cff0b9f1e3f3356ccc6395d5c5a8d0af9cf110678"
+    );
+}
+
+#[test]
+fn test_pedersen_commit_two_fields() {
+    let (x, y) = pedersen_commit(&[field(1), field(2)]);
+    assert_eq!(
+        hex::encode(x),
+        "092d73d58bf37ff31acb813bb9b3b8aef71ef86db847bc92e9513bcbd98f7ac7"
+    );
+    assert_eq!(
+        hex::encode(y),
+        "12dad8909f6505998b0a24e44d158cca67c9a3aaa16c4767a9513529cdcd9a4d"
+    );
+}
+
+#[test]
+fn test_pedersen_commit_empty_is_point_at_infinity() {
+    assert_eq!(pedersen_commit(&[]), (POINT_AT_INFINITY_X, [0u8; 32]));
+}
@@ -0,0 +1,70 @@
+use std::borrow::Borrow;
+
+use crate::{
+    buffer::InputBuffer, error::lock_backend, schnorr_compute_public_key,
+    schnorr_construct_signature, schnorr_verify_signature,
+};
+
+#[cfg(test)]
+pub mod test;
+
+/// Derives the Grumpkin public key of `private_key`, returned as big-endian `(x, y)` coordinates.
+///
+/// The key is a `&[u8; 32]`, or with the `zeroize` feature a `&SecretKey` that is scrubbed when
+/// dropped.
+pub fn compute_public_key<K>(private_key: &K) -> ([u8; 32], [u8; 32])
+where
+    K: Borrow<[u8; 32]> + ?Sized,
+{
+    let private_key: &[u8; 32] = private_key.borrow();
+    let mut output = [0u8; 64];
+    let _backend = lock_backend();
+    unsafe { schnorr_compute_public_key(private_key.as_ptr(), output.as_mut_ptr()) };
+    let (x, y) = output.split_at(32);
+    (x.try_into().unwrap(), y.try_into().unwrap())
+}
+
+/// Signs `message` with barretenberg's Blake2s-based Schnorr scheme, returning the `(s, e)` pair.
+///
+/// The key is taken as by [`compute_public_key`].
+pub fn construct_signature<K>(message: &[u8], private_key: &K) -> ([u8; 32], [u8; 32])
+where
+    K: Borrow<[u8; 32]> + ?Sized,
+{
+    let private_key: &[u8; 32] = private_key.borrow();
+    let mut s = [0u8; 32];
+    let mut e = [0u8; 32];
+    let _backend = lock_backend();
+    unsafe {
+        schnorr_construct_signature(
+            InputBuffer::new(message).serialize().as_ptr(),
+            private_key.as_ptr(),
+            s.as_mut_ptr(),
+            e.as_mut_ptr(),
+        )
+    };
+    (s, e)
+}
+
+/// Checks the `(s, e)` signature `sig` of `message` against the `(x, y)` public key `pub_key`.
+pub fn verify_signature(
+    message: &[u8],
+    pub_key: ([u8; 32], [u8; 32]),
+    sig: ([u8; 32], [u8; 32]),
+) -> bool {
+    let mut pub_key_buf = [0u8; 64];
+    pub_key_buf[..32].copy_from_slice(&pub_key.0);
+    pub_key_buf[32..].copy_from_slice(&pub_key.1);
+    let mut result = false;
+    let _backend = lock_backend();
+    unsafe {
+        schnorr_verify_signature(
+            InputBuffer::new(message).serialize().as_ptr(),
+            pub_key_buf.as_ptr(),
+            sig.0.as_ptr(),
+            sig.1.as_ptr(),
+            &mut result,
+        )
+    };
+    result
+}
@@ -0,0 +1,28 @@
+use super::{compute_public_key, construct_signature, verify_signature};
+
+fn private_key() -> [u8; 32] {
+    let mut bytes = [0u8; 32];
+    bytes[31] = 7;
+    bytes
+}
+
+#[test]
+fn test_schnorr_sign_then_verify() {
+    let private_key = private_key();
+    let public_key = compute_public_key(&private_key);
+    let message = b"The quick brown fox jumped over the lazy dog.";
+
+    let signature = construct_signature(message, &private_key);
+    assert!(verify_signature(message, public_key, signature));
+}
+
+#[test]
+fn test_schnorr_rejects_tampered_signature() {
+    let private_key = private_key();
+    let public_key = compute_public_key(&private_key);
+    let message = b"The quick brown fox jumped over the lazy dog.";
+
+    let (mut s, e) = construct_signature(message, &private_key);
+    s[31] ^= 1;
+    assert!(!verify_signature(message, public_key, (s, e)));
+}
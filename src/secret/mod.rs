@@ -0,0 +1,60 @@
+use std::borrow::Borrow;
+use std::fmt;
+
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+#[cfg(test)]
+pub mod test;
+
+/// A 32-byte private key that is overwritten with zeros when dropped.
+///
+/// The signing functions, such as [`crate::schnorr::construct_signature`], take it wherever they
+/// take a `&[u8; 32]`, and read the key in place. Only the bytes held here are scrubbed: the array
+/// the key was created from is a copy the caller must zeroize itself.
+#[derive(Clone)]
+#[repr(transparent)]
+pub struct SecretKey([u8; 32]);
+
+impl SecretKey {
+    pub fn new(bytes: [u8; 32]) -> Self {
+        SecretKey(bytes)
+    }
+
+    /// The raw key bytes.
+    pub fn expose_secret(&self) -> &[u8; 32] {
+        &self.0
+    }
+}
+
+impl From<[u8; 32]> for SecretKey {
+    fn from(bytes: [u8; 32]) -> Self {
+        SecretKey::new(bytes)
+    }
+}
+
+impl Borrow<[u8; 32]> for SecretKey {
+    fn borrow(&self) -> &[u8; 32] {
+        &self.0
+    }
+}
+
+impl Zeroize for SecretKey {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl Drop for SecretKey {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for SecretKey {}
+
+/// Never prints the key, so it cannot end up in logs.
+impl fmt::Debug for SecretKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SecretKey(..)")
+    }
+}
@@ -0,0 +1,42 @@
+use std::mem::MaybeUninit;
+
+use zeroize::Zeroize;
+
+use super::SecretKey;
+use crate::schnorr::{compute_public_key, construct_signature, verify_signature};
+
+#[test]
+fn test_secret_key_is_zeroed_on_drop() {
+    let mut slot = MaybeUninit::new(SecretKey::from([0xaa; 32]));
+    let bytes = slot.as_ptr().cast::<[u8; 32]>();
+    assert_eq!(unsafe { bytes.read() }, [0xaa; 32]);
+
+    // The storage outlives the key, so what the drop left behind can still be read.
+    unsafe { slot.assume_init_drop() };
+    assert_eq!(unsafe { bytes.read() }, [0u8; 32]);
+}
+
+#[test]
+fn test_secret_key_zeroize() {
+    let mut key = SecretKey::new([7u8; 32]);
+    key.zeroize();
+    assert_eq!(key.expose_secret(), &[0u8; 32]);
+}
+
+#[test]
+fn test_secret_key_debug_hides_bytes() {
+    assert_eq!(format!("{:?}", SecretKey::new([0xaa; 32])), "SecretKey(..)");
+}
+
+#[test]
+fn test_schnorr_accepts_secret_key() {
+    let mut bytes = [0u8; 32];
+    bytes[31] = 7;
+    let key = SecretKey::new(bytes);
+    let public_key = compute_public_key(&key);
+    assert_eq!(public_key, compute_public_key(&bytes));
+
+    let message = b"The quick brown fox jumped over the lazy dog.";
+    let signature = construct_signature(message, &key);
+    assert!(verify_signature(message, public_key, signature));
+}
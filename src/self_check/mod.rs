@@ -0,0 +1,105 @@
+use std::io::Read;
+
+use base64::{engine::general_purpose, Engine};
+use flate2::read::GzDecoder;
+
+use crate::acir_proofs::acir_composer::{get_circuit_sizes, AcirComposer};
+use crate::acir_proofs::proof::ProofMode;
+use crate::error::{ffi_call, BackendError, Operation};
+use crate::examples_simple_create_and_verify_proof;
+use crate::srs;
+
+#[cfg(test)]
+pub mod test;
+
+/// A gzipped, base64-encoded ACIR circuit with 5 gates and a single public input.
+pub(crate) const CIRCUIT: &str = "H4sIAAAAAAAA/7WTMRLEIAhFMYkp9ywgGrHbq6yz5v5H2JkdCyaxC9LgWDw+H9gBwMM91p7fPeOzIKdYjEeMLYdGTB8MpUrCmOohJJQkfYMwN4mSSy0ZC0VudKbCZ4cthqzVrsc/yw28dMZeWmrWerfBexnsxD6hJ7jUufr4GvyZFp8xpG0C14Pd8s/q29vPCBXypvmpDx7sD8opnfqIfsM1RNtxBQAA";
+/// A solved witness for [`CIRCUIT`].
+pub(crate) const WITNESS: &str = "05000000000000000100000040000000000000003030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303302000000400000000000000030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303034030000004000000000000000333036343465373265313331613032396238353034356236383138313538356432383333653834383739623937303931343365316635393366303030303030300400000040000000000000003330363434653732653133316130323962383530343562363831383135383564323833336538343837396239373039313433653166353933663030303030303005000000400000000000000030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030";
+
+/// Circuit sizes barretenberg reports for [`CIRCUIT`], as `(exact, total, subgroup)`.
+const EXPECTED_SIZES: (u32, u32, u32) = (5, 10, 16);
+
+/// Runs a tiny known circuit through the linked barretenberg and checks the results.
+///
+/// This catches a `libbarretenberg.a` that does not match these bindings, which otherwise shows
+/// up as garbage circuit sizes or crashes in the middle of proving. The circuit sizes are
+/// compared against known values, then a proof is created and verified. The CRS must already
+/// hold at least 17 points, see [`crate::srs::srs_init`]; a smaller CRS is refused with
+/// [`BackendError::CrsTooSmall`], as the backend would abort the process.
+pub fn self_check() -> Result<(), BackendError> {
+    let circuit = decode_circuit();
+    let witness = hex::decode(WITNESS).expect("the fixture witness is valid hex");
+
+    let sizes = get_circuit_sizes(&circuit)?;
+    if (sizes.exact, sizes.total, sizes.subgroup) != EXPECTED_SIZES {
+        return Err(BackendError::SelfCheckFailed {
+            op: Operation::new("acir_get_circuit_sizes").input("cs", circuit.len()),
+            reason: format!(
+                "expected circuit sizes {:?}, got {:?}; the linked library does not match \
+                 these bindings",
+                EXPECTED_SIZES,
+                (sizes.exact, sizes.total, sizes.subgroup)
+            ),
+        });
+    }
+
+    let needed = sizes.subgroup + 1;
+    let num_points = srs::num_points();
+    if num_points < needed {
+        return Err(BackendError::CrsTooSmall {
+            op: Operation::new("acir_create_proof")
+                .input("cs", circuit.len())
+                .size_hint(sizes.subgroup),
+            num_points,
+            needed,
+        });
+    }
+
+    let acir_composer = AcirComposer::new(sizes.subgroup)?;
+    let proof = acir_composer.create_proof(&circuit, &witness, ProofMode::Standard)?;
+    if !acir_composer.verify_proof(&proof, ProofMode::Standard)? {
+        return Err(BackendError::SelfCheckFailed {
+            op: Operation::new("acir_verify_proof")
+                .input("proof", proof.as_ref().len())
+                .size_hint(sizes.subgroup),
+            reason: "a proof of the fixture circuit did not verify".to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// The number of CRS points [`prove_and_verify_example`] needs: its circuit has 2^19 gates.
+pub const EXAMPLE_CRS_POINTS: u32 = (1 << 19) + 1;
+
+/// Proves and verifies barretenberg's own example circuit, returning whether the proof verified.
+///
+/// Unlike [`self_check`], this needs no circuit from these bindings, so it tells whether the
+/// library itself links and works. The circuit is large: the CRS must already hold
+/// [`EXAMPLE_CRS_POINTS`] points, and proving takes seconds even in release builds. A smaller
+/// CRS is refused with [`BackendError::CrsTooSmall`], as the backend would abort the process.
+pub fn prove_and_verify_example() -> Result<bool, BackendError> {
+    let op = Operation::new("examples_simple_create_and_verify_proof");
+    let num_points = srs::num_points();
+    if num_points < EXAMPLE_CRS_POINTS {
+        return Err(BackendError::CrsTooSmall {
+            op,
+            num_points,
+            needed: EXAMPLE_CRS_POINTS,
+        });
+    }
+    let mut valid = false;
+    ffi_call!(op, examples_simple_create_and_verify_proof(&mut valid))?;
+    Ok(valid)
+}
+
+fn decode_circuit() -> Vec<u8> {
+    let compressed = general_purpose::STANDARD
+        .decode(CIRCUIT)
+        .expect("the fixture circuit is valid base64");
+    let mut circuit = Vec::new();
+    GzDecoder::new(compressed.as_slice())
+        .read_to_end(&mut circuit)
+        .expect("the fixture circuit is valid gzip");
+    circuit
+}
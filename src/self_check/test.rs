@@ -0,0 +1,59 @@
+use super::{prove_and_verify_example, self_check, EXAMPLE_CRS_POINTS};
+use crate::acir_proofs::test::init_srs;
+use crate::dealloc::test::{is_child, run_in_child};
+use crate::error::{BackendError, BackendErrorKind};
+use crate::srs::{netsrs::NetSrs, srs_init};
+
+#[test]
+fn test_self_check_passes() {
+    init_srs();
+    self_check().unwrap();
+}
+
+#[test]
+fn test_self_check_reports_small_crs() {
+    if !is_child() {
+        // The CRS is too small for the other tests, so it is set up in a process of its own.
+        let status = run_in_child("self_check::test::test_self_check_reports_small_crs");
+        assert!(status.success(), "{status}");
+        return;
+    }
+    let srs = NetSrs::new(8);
+    srs_init(&srs.g1_data, srs.num_points, &srs.g2_data).unwrap();
+    assert!(matches!(
+        self_check(),
+        Err(BackendError::CrsTooSmall {
+            num_points: 8,
+            needed: 17,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn test_prove_and_verify_example_reports_small_crs() {
+    init_srs();
+    let err = prove_and_verify_example().unwrap_err();
+    assert!(matches!(
+        err,
+        BackendError::CrsTooSmall {
+            needed: EXAMPLE_CRS_POINTS,
+            ..
+        }
+    ));
+    assert_eq!(err.kind(), BackendErrorKind::CrsTooSmall);
+}
+
+#[test]
+#[ignore = "slow; downloads a CRS of 2^19 points and proves the example circuit"]
+fn test_prove_and_verify_example() {
+    if !is_child() {
+        // The example needs a larger CRS than the other tests share, so it runs on its own.
+        let status = run_in_child("self_check::test::test_prove_and_verify_example");
+        assert!(status.success(), "{status}");
+        return;
+    }
+    let srs = NetSrs::new(EXAMPLE_CRS_POINTS);
+    srs_init(&srs.g1_data, srs.num_points, &srs.g2_data).unwrap();
+    assert_eq!(prove_and_verify_example(), Ok(true));
+}
@@ -0,0 +1,31 @@
+use super::{srs_init_once, SrsGuard, G1_POINT_SIZE, G2_POINT_SIZE};
+use crate::error::{BackendError, Operation};
+
+/// The G1 points and G2 point the build script copied from `BARRETENBERG_EMBEDDED_SRS`, or
+/// nothing if it was not set.
+const EMBEDDED_SRS: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/embedded_srs.bin"));
+
+/// The number of G1 points embedded, `BARRETENBERG_EMBEDDED_SRS_POINTS` when building or 4097, and
+/// zero if no SRS was embedded.
+pub const EMBEDDED_SRS_POINTS: u32 =
+    (EMBEDDED_SRS.len().saturating_sub(G2_POINT_SIZE) / G1_POINT_SIZE) as u32;
+
+/// Initializes the process-wide CRS with the SRS embedded in the binary, through
+/// [`srs_init_once`], so nothing has to be read or downloaded at run time.
+///
+/// Each embedded point adds 64 bytes to the binary, so the default 4097 points, enough for
+/// circuits of up to 4096 gates after padding, cost about 256 KiB.
+///
+/// Fails with [`BackendError::MalformedInput`] if `BARRETENBERG_EMBEDDED_SRS` was not set when
+/// building, so no SRS was embedded.
+pub fn srs_init_embedded() -> Result<SrsGuard, BackendError> {
+    if EMBEDDED_SRS_POINTS == 0 {
+        return Err(BackendError::MalformedInput {
+            op: Operation::new("srs_init_srs"),
+            reason: "no SRS was embedded, as BARRETENBERG_EMBEDDED_SRS was not set when building"
+                .to_owned(),
+        });
+    }
+    let (points, g2_point) = EMBEDDED_SRS.split_at(EMBEDDED_SRS.len() - G2_POINT_SIZE);
+    srs_init_once(points, EMBEDDED_SRS_POINTS, g2_point)
+}
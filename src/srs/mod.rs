@@ -1,22 +1,213 @@
+use std::fmt;
+use std::fs::File;
+use std::io::{self, Read, Seek, SeekFrom};
+use std::path::Path;
+#[cfg(test)]
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Mutex, Once};
+
 use crate::{
-    buffer::{parse_c_str, serialize_slice},
+    error::{ffi_call, BackendError, Operation},
     srs_init_srs,
 };
 
+#[cfg(feature = "embedded-srs")]
+pub mod embedded;
 pub mod netsrs;
+#[cfg(test)]
+pub mod test;
+
+/// Size of the manifest at the start of an ignition transcript: seven big-endian `u32`s.
+const MANIFEST_SIZE: usize = 28;
+/// Size of an uncompressed affine BN254 G1 point.
+const G1_POINT_SIZE: usize = 64;
+/// Size of an uncompressed affine BN254 G2 point.
+const G2_POINT_SIZE: usize = 128;
+
+/// Guards the single call [`srs_init_once`] makes into the backend.
+static INIT: Once = Once::new();
+/// The outcome of that call: the number of points loaded, or why the backend rejected them.
+static INIT_RESULT: Mutex<Option<Result<u32, BackendError>>> = Mutex::new(None);
+/// The number of G1 points of the CRS the backend last accepted, zero before the first.
+static NUM_POINTS: AtomicU32 = AtomicU32::new(0);
+/// How many times [`srs_init_once`] has called into the backend.
+#[cfg(test)]
+static INIT_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+/// Initializes barretenberg's process-wide CRS with the first `num_points` G1 points in
+/// `points_buf` and the G2 point in `g2_point_buf`.
+///
+/// Both buffers hold points in the raw transcript encoding, without a length prefix. The backend
+/// reads exactly `num_points * 64` and `128` bytes from them, so shorter buffers are rejected
+/// before the call.
+///
+/// Each call replaces the CRS. A process should use either this or [`srs_init_once`], not both:
+/// calling this after [`srs_init_once`] is unsupported, as it replaces the CRS an [`SrsGuard`]
+/// vouches for without telling the guard, and later calls to [`srs_init_once`] still return the
+/// first outcome. [`srs_init_from_path`] calls this as well.
+pub fn srs_init(
+    points_buf: &[u8],
+    num_points: u32,
+    g2_point_buf: &[u8],
+) -> Result<(), BackendError> {
+    let op = check_srs_inputs(points_buf, num_points, g2_point_buf)?;
+    ffi_call!(op, srs_init_srs(points_buf.as_ptr(), &num_points, g2_point_buf.as_ptr()))?;
+    NUM_POINTS.store(num_points, Ordering::SeqCst);
+    Ok(())
+}
+
+/// The number of G1 points the process-wide CRS was last initialized with, or zero if it was
+/// not.
+///
+/// Calls that build their own circuit inside the backend can check it first: barretenberg
+/// aborts the process instead of throwing when their circuit outgrows the CRS.
+pub fn num_points() -> u32 {
+    NUM_POINTS.load(Ordering::SeqCst)
+}
+
+/// Rejects buffers too short for the backend to read the points from, returning the operation to
+/// report the call as otherwise.
+fn check_srs_inputs(
+    points_buf: &[u8],
+    num_points: u32,
+    g2_point_buf: &[u8],
+) -> Result<Operation, BackendError> {
+    let op = Operation::new("srs_init_srs")
+        .input("points", points_buf.len())
+        .input("g2_point", g2_point_buf.len());
+    let points_len = num_points as usize * G1_POINT_SIZE;
+    if points_buf.len() < points_len {
+        return Err(BackendError::MalformedInput {
+            op,
+            reason: format!("{num_points} G1 points need {points_len} bytes"),
+        });
+    }
+    if g2_point_buf.len() < G2_POINT_SIZE {
+        return Err(BackendError::MalformedInput {
+            op,
+            reason: format!("a G2 point needs {G2_POINT_SIZE} bytes"),
+        });
+    }
+    Ok(op)
+}
+
+/// Proof that the process-wide CRS has been initialized by [`srs_init_once`].
+///
+/// Dropping the guard does not de-initialize the CRS: barretenberg holds it globally for the rest
+/// of the process, and there is no entry point to release it. The guard only holds while nothing
+/// calls [`srs_init`] as well, which is unsupported.
+#[derive(Debug)]
+pub struct SrsGuard {
+    num_points: u32,
+}
+
+impl SrsGuard {
+    /// The number of G1 points the CRS was initialized with, which is the `num_points` of the
+    /// first successful call to [`srs_init_once`], not necessarily of the call that returned this
+    /// guard.
+    pub fn num_points(&self) -> u32 {
+        self.num_points
+    }
+}
+
+/// Like [`srs_init`], but only the first call reaches the backend; later calls, including
+/// concurrent ones, wait for it to finish and return its outcome without touching the CRS.
+///
+/// Inputs that [`srs_init`] would reject before calling into the backend are rejected without
+/// counting as the first call. A failure reported by the backend itself is not retried: every
+/// later call returns the same error.
+///
+/// Mixing this with [`srs_init`] in one process is unsupported, see there.
+pub fn srs_init_once(
+    points_buf: &[u8],
+    num_points: u32,
+    g2_point_buf: &[u8],
+) -> Result<SrsGuard, BackendError> {
+    check_srs_inputs(points_buf, num_points, g2_point_buf)?;
+
+    INIT.call_once(|| {
+        #[cfg(test)]
+        INIT_CALLS.fetch_add(1, Ordering::SeqCst);
+        let result = srs_init(points_buf, num_points, g2_point_buf).map(|()| num_points);
+        *INIT_RESULT.lock().unwrap() = Some(result);
+    });
+    let result = INIT_RESULT.lock().unwrap().clone();
+    match result.expect("INIT_RESULT is set once INIT has completed") {
+        Ok(num_points) => Ok(SrsGuard { num_points }),
+        Err(err) => Err(err),
+    }
+}
+
+/// Initializes the CRS with the first `num_points` points of a local ignition transcript, such
+/// as `monomial/transcript00.dat`.
+///
+/// Only the requested G1 points and the G2 point that follows the transcript's G1 section are
+/// read, so a small CRS can be loaded from the full transcript without reading all of it.
+pub fn srs_init_from_path(path: &Path, num_points: u32) -> Result<(), SrsError> {
+    let (points, g2_point) = read_transcript(path, num_points)?;
+    Ok(srs_init(&points, num_points, &g2_point)?)
+}
+
+/// Reads the first `num_points` G1 points and the G2 point of a local ignition transcript, for
+/// [`srs_init_from_path`] or [`srs_init_once`].
+pub fn read_transcript(path: &Path, num_points: u32) -> io::Result<(Vec<u8>, [u8; G2_POINT_SIZE])> {
+    let mut file = File::open(path)?;
+    let mut manifest = [0u8; MANIFEST_SIZE];
+    file.read_exact(&mut manifest)?;
+    // `num_g1_points` is the fifth field of the manifest.
+    let num_g1_points = u32::from_be_bytes(manifest[16..20].try_into().unwrap());
+    if num_points > num_g1_points {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("transcript holds {num_g1_points} G1 points, {num_points} requested"),
+        ));
+    }
+
+    let mut points = vec![0u8; num_points as usize * G1_POINT_SIZE];
+    file.read_exact(&mut points)?;
+    let g2_offset = MANIFEST_SIZE as u64 + u64::from(num_g1_points) * G1_POINT_SIZE as u64;
+    file.seek(SeekFrom::Start(g2_offset))?;
+    let mut g2_point = [0u8; G2_POINT_SIZE];
+    file.read_exact(&mut g2_point)?;
+    Ok((points, g2_point))
+}
+
+/// Errors from [`srs_init_from_path`].
+#[derive(Debug)]
+pub enum SrsError {
+    /// The transcript could not be read, or holds fewer points than requested.
+    Io(io::Error),
+    /// The backend rejected the points.
+    Backend(BackendError),
+}
+
+impl fmt::Display for SrsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SrsError::Io(err) => write!(f, "failed to read the SRS transcript: {err}"),
+            SrsError::Backend(err) => err.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for SrsError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SrsError::Io(err) => Some(err),
+            SrsError::Backend(err) => Some(err),
+        }
+    }
+}
+
+impl From<io::Error> for SrsError {
+    fn from(err: io::Error) -> Self {
+        SrsError::Io(err)
+    }
+}
 
-pub fn srs_init(points_buf: &[u8], num_points: u32, g2_point_buf: &[u8]) {
-    let error_msg_ptr = unsafe {
-        srs_init_srs(
-            serialize_slice(points_buf).as_slice().as_ptr(),
-            &num_points,
-            serialize_slice(g2_point_buf).as_slice().as_ptr(),
-        )
-    };
-    if !error_msg_ptr.is_null() {
-        println!(
-            "C++ error: {}",
-            parse_c_str(error_msg_ptr).unwrap_or("Parsing c_str failed".to_string())
-        );
+impl From<BackendError> for SrsError {
+    fn from(err: BackendError) -> Self {
+        SrsError::Backend(err)
     }
 }
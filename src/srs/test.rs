@@ -0,0 +1,121 @@
+use std::io;
+use std::path::Path;
+use std::sync::atomic::Ordering;
+use std::thread;
+
+use super::{netsrs::NetSrs, srs_init, srs_init_from_path, srs_init_once, SrsError, INIT_CALLS};
+use crate::acir_proofs::acir_composer::AcirComposer;
+use crate::acir_proofs::proof::ProofMode;
+use crate::acir_proofs::test::{acir_buffer_uncompressed, init_srs, witness, TEST_SRS_POINTS};
+use crate::error::BackendError;
+
+/// Writes an ignition-style transcript holding the G1 points in `g1_data` and one G2 point.
+pub(crate) fn write_transcript(path: &Path, g1_data: &[u8], g2_data: &[u8]) {
+    let num_g1_points = (g1_data.len() / 64) as u32;
+    let manifest = [0, 1, num_g1_points, 1, num_g1_points, 1, 0];
+    let mut transcript: Vec<u8> = manifest.iter().flat_map(|field| field.to_be_bytes()).collect();
+    transcript.extend_from_slice(g1_data);
+    transcript.extend_from_slice(g2_data);
+    std::fs::write(path, transcript).unwrap();
+}
+
+#[test]
+fn test_srs_init_rejects_short_points_buffer() {
+    let result = srs_init(&[0u8; 100], 2, &[0u8; 128]);
+    match result {
+        Err(BackendError::MalformedInput { op, .. }) => assert_eq!(op.name, "srs_init_srs"),
+        other => panic!("expected malformed input, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_srs_init_once_rejects_short_points_buffer_without_initializing() {
+    let result = srs_init_once(&[0u8; 100], 2, &[0u8; 128]);
+    assert!(matches!(result, Err(BackendError::MalformedInput { .. })));
+
+    init_srs();
+    assert_eq!(INIT_CALLS.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn test_srs_init_once_from_many_threads() {
+    // Other tests may have initialized the CRS already; all of them go through `init_srs`, which
+    // uses the same points.
+    let srs = NetSrs::new(TEST_SRS_POINTS);
+    let guards: Vec<_> = thread::scope(|scope| {
+        let handles: Vec<_> = (0..8)
+            .map(|_| scope.spawn(|| srs_init_once(&srs.g1_data, srs.num_points, &srs.g2_data)))
+            .collect();
+        handles.into_iter().map(|handle| handle.join().unwrap()).collect()
+    });
+
+    for guard in guards {
+        assert_eq!(guard.unwrap().num_points(), TEST_SRS_POINTS);
+    }
+    assert_eq!(INIT_CALLS.load(Ordering::SeqCst), 1);
+
+    // The CRS is usable, and initializing again through the test helper is a no-op.
+    init_srs();
+    assert_eq!(INIT_CALLS.load(Ordering::SeqCst), 1);
+    let acir_composer = AcirComposer::new(16).unwrap();
+    let proof = acir_composer
+        .create_proof(&acir_buffer_uncompressed(), &witness(), ProofMode::Standard)
+        .unwrap();
+    assert!(acir_composer.verify_proof(&proof, ProofMode::Standard).unwrap());
+}
+
+#[test]
+fn test_srs_init_from_path_rejects_missing_file() {
+    let result = srs_init_from_path(Path::new("/nonexistent/transcript00.dat"), 1);
+    assert!(matches!(result, Err(SrsError::Io(err)) if err.kind() == io::ErrorKind::NotFound));
+}
+
+#[test]
+fn test_srs_init_from_path_rejects_too_many_points() {
+    let path = std::env::temp_dir().join(format!(
+        "barretenberg-sys-{}-short-transcript.dat",
+        std::process::id()
+    ));
+    write_transcript(&path, &[0u8; 2 * 64], &[0u8; 128]);
+
+    let result = srs_init_from_path(&path, 3);
+    let _ = std::fs::remove_file(&path);
+    assert!(matches!(result, Err(SrsError::Io(err)) if err.kind() == io::ErrorKind::InvalidData));
+}
+
+#[test]
+fn test_srs_from_transcript_proves_circuit() {
+    init_srs();
+    let acir_composer = AcirComposer::new(16).unwrap();
+
+    let proof = acir_composer
+        .create_proof(&acir_buffer_uncompressed(), &witness(), ProofMode::Standard)
+        .unwrap();
+    assert!(acir_composer.verify_proof(&proof, ProofMode::Standard).unwrap());
+}
+
+#[cfg(feature = "embedded-srs")]
+#[test]
+fn test_prove_with_embedded_srs() {
+    use super::embedded::{srs_init_embedded, EMBEDDED_SRS_POINTS};
+    use crate::dealloc::test::{is_child, run_in_child};
+
+    if !is_child() {
+        // The CRS must come from the embedded points alone, so this runs in a process of its own.
+        let status = run_in_child("srs::test::test_prove_with_embedded_srs");
+        assert!(status.success(), "{status}");
+        return;
+    }
+    if EMBEDDED_SRS_POINTS == 0 {
+        // Built without `BARRETENBERG_EMBEDDED_SRS`.
+        assert!(matches!(srs_init_embedded(), Err(BackendError::MalformedInput { .. })));
+        return;
+    }
+    let guard = srs_init_embedded().unwrap();
+    assert_eq!(guard.num_points(), EMBEDDED_SRS_POINTS);
+    let acir_composer = AcirComposer::new(16).unwrap();
+    let proof = acir_composer
+        .create_proof(&acir_buffer_uncompressed(), &witness(), ProofMode::Standard)
+        .unwrap();
+    assert!(acir_composer.verify_proof(&proof, ProofMode::Standard).unwrap());
+}
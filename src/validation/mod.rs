@@ -0,0 +1,121 @@
+#[cfg(test)]
+pub mod test;
+
+/// Size of a bincode sequence or map length, a little-endian u64.
+const LEN_SIZE: usize = 8;
+/// Size of a witness index, a little-endian u32.
+const WITNESS_INDEX_SIZE: usize = 4;
+/// Size of an uncompressed affine BN254 G1 point.
+const G1_AFFINE_SIZE: usize = 64;
+/// Size of the smallest serialized opcode, which is only its variant index.
+const MIN_OPCODE_SIZE: u64 = 4;
+/// The private parameters, public parameters, return values and assert messages that follow the
+/// opcodes of a circuit each take at least their length.
+const MIN_CIRCUIT_TRAILER_SIZE: u64 = 4 * LEN_SIZE as u64;
+
+/// Checks that the start of a bincode-serialized ACIR `Circuit` agrees with the length of
+/// `constraint_system_buf`.
+///
+/// Only the header is checked: the witness index and the opcode count, which must leave room for
+/// that many opcodes and the sequences that follow them. Anything past that is left to the
+/// backend's deserializer, which reports inconsistencies as an error.
+pub fn constraint_system(constraint_system_buf: &[u8]) -> Result<(), String> {
+    let header_len = WITNESS_INDEX_SIZE + LEN_SIZE;
+    if constraint_system_buf.len() < header_len {
+        return Err(format!(
+            "a circuit starts with a {header_len}-byte header, got {} bytes",
+            constraint_system_buf.len()
+        ));
+    }
+    let (header, rest) = constraint_system_buf.split_at(header_len);
+    let num_opcodes = u64::from_le_bytes(header[WITNESS_INDEX_SIZE..].try_into().unwrap());
+    let min_len = num_opcodes
+        .checked_mul(MIN_OPCODE_SIZE)
+        .and_then(|len| len.checked_add(MIN_CIRCUIT_TRAILER_SIZE));
+    match min_len {
+        Some(min_len) if min_len <= rest.len() as u64 => Ok(()),
+        _ => Err(format!(
+            "{num_opcodes} opcodes cannot fit in the {} bytes after the circuit header",
+            rest.len()
+        )),
+    }
+}
+
+/// Checks that a bincode-serialized ACIR `WitnessMap` holds exactly as many entries as it claims
+/// and nothing after them.
+///
+/// Every entry is a witness index followed by a length-prefixed string, so the whole buffer is
+/// walked; this is cheap next to proving.
+pub fn witness(witness_buf: &[u8]) -> Result<(), String> {
+    let (num_entries, mut rest) = split_len(witness_buf)
+        .ok_or_else(|| format!("a witness map starts with a {LEN_SIZE}-byte entry count"))?;
+    for i in 0..num_entries {
+        let (value_len, value) = rest
+            .get(WITNESS_INDEX_SIZE..)
+            .and_then(split_len)
+            .ok_or_else(|| format!("witness entry {i} of {num_entries} is truncated"))?;
+        rest = usize::try_from(value_len)
+            .ok()
+            .and_then(|len| value.get(len..))
+            .ok_or_else(|| {
+                format!(
+                    "witness entry {i} claims a {value_len}-byte value, only {} remain",
+                    value.len()
+                )
+            })?;
+    }
+    if !rest.is_empty() {
+        return Err(format!(
+            "{} bytes follow the {num_entries} witness entries",
+            rest.len()
+        ));
+    }
+    Ok(())
+}
+
+/// Checks that a serialized `verification_key_data`, as returned by
+/// [`crate::acir_proofs::acir_composer::AcirComposer::get_verification_key`], is exactly as long
+/// as its fields claim.
+///
+/// Unlike the ACIR buffers it uses barretenberg's own big-endian serialization: the circuit type,
+/// size and number of public inputs, a map from commitment labels to G1 points, a flag for
+/// recursive proofs and the vector of their public input indices. The backend reads it without
+/// any outer length, so every length is checked.
+pub fn verification_key(verification_key_buf: &[u8]) -> Result<(), String> {
+    let truncated = || "verification key is truncated".to_string();
+    let rest = verification_key_buf.get(3 * 4..).ok_or_else(truncated)?;
+    let (num_commitments, mut rest) = split_u32_be(rest).ok_or_else(truncated)?;
+    for _ in 0..num_commitments {
+        let (label_len, label) = split_u32_be(rest).ok_or_else(truncated)?;
+        rest = (label_len as usize)
+            .checked_add(G1_AFFINE_SIZE)
+            .and_then(|len| label.get(len..))
+            .ok_or_else(truncated)?;
+    }
+    // Skip the one-byte recursive proof flag.
+    let (num_indices, indices) = rest.get(1..).and_then(split_u32_be).ok_or_else(truncated)?;
+    let indices_len = u64::from(num_indices) * 4;
+    match (indices.len() as u64).checked_sub(indices_len) {
+        Some(0) => Ok(()),
+        Some(extra) => Err(format!("{extra} bytes follow the verification key")),
+        None => Err(truncated()),
+    }
+}
+
+/// Splits a big-endian u32 length, as barretenberg serializes it, off the front of `bytes`.
+fn split_u32_be(bytes: &[u8]) -> Option<(u32, &[u8])> {
+    if bytes.len() < 4 {
+        return None;
+    }
+    let (len, rest) = bytes.split_at(4);
+    Some((u32::from_be_bytes(len.try_into().unwrap()), rest))
+}
+
+/// Splits a bincode length off the front of `bytes`.
+fn split_len(bytes: &[u8]) -> Option<(u64, &[u8])> {
+    if bytes.len() < LEN_SIZE {
+        return None;
+    }
+    let (len, rest) = bytes.split_at(LEN_SIZE);
+    Some((u64::from_le_bytes(len.try_into().unwrap()), rest))
+}
@@ -0,0 +1,84 @@
+use super::{constraint_system, verification_key, witness};
+use crate::acir_proofs::test::{acir_buffer_uncompressed, witness as fixture_witness};
+
+#[test]
+fn test_accepts_fixtures() {
+    assert_eq!(constraint_system(&acir_buffer_uncompressed()), Ok(()));
+    assert_eq!(witness(&fixture_witness()), Ok(()));
+}
+
+#[test]
+fn test_rejects_empty_buffers() {
+    assert!(constraint_system(&[]).is_err());
+    assert!(witness(&[]).is_err());
+}
+
+#[test]
+fn test_rejects_circuit_with_too_many_opcodes() {
+    let mut circuit = acir_buffer_uncompressed();
+    circuit[4..12].copy_from_slice(&u64::MAX.to_le_bytes());
+    assert_eq!(
+        constraint_system(&circuit),
+        Err(format!(
+            "{} opcodes cannot fit in the {} bytes after the circuit header",
+            u64::MAX,
+            circuit.len() - 12
+        ))
+    );
+
+    let truncated = &acir_buffer_uncompressed()[..20];
+    assert!(constraint_system(truncated).is_err());
+}
+
+#[test]
+fn test_rejects_truncated_witness() {
+    let witness_buf = fixture_witness();
+    let err = witness(&witness_buf[..witness_buf.len() - 1]).unwrap_err();
+    assert_eq!(
+        err,
+        "witness entry 4 claims a 64-byte value, only 63 remain"
+    );
+
+    assert_eq!(
+        witness(&witness_buf[..10]),
+        Err("witness entry 0 of 5 is truncated".to_string())
+    );
+}
+
+#[test]
+fn test_rejects_over_long_witness() {
+    let mut witness_buf = fixture_witness();
+    witness_buf.push(0);
+    assert_eq!(
+        witness(&witness_buf),
+        Err("1 bytes follow the 5 witness entries".to_string())
+    );
+}
+
+#[test]
+fn test_verification_key_lengths() {
+    // No commitments and no recursive proof indices.
+    let mut vk = vec![0u8; 12];
+    vk.extend_from_slice(&0u32.to_be_bytes());
+    vk.push(0);
+    vk.extend_from_slice(&0u32.to_be_bytes());
+    assert_eq!(verification_key(&vk), Ok(()));
+
+    // One commitment labelled "Q_1".
+    let mut with_commitment = vk[..12].to_vec();
+    with_commitment.extend_from_slice(&1u32.to_be_bytes());
+    with_commitment.extend_from_slice(&3u32.to_be_bytes());
+    with_commitment.extend_from_slice(b"Q_1");
+    with_commitment.extend_from_slice(&[0; 64]);
+    with_commitment.extend_from_slice(&vk[16..]);
+    assert_eq!(verification_key(&with_commitment), Ok(()));
+
+    assert_eq!(
+        verification_key(&with_commitment[..with_commitment.len() - 1]),
+        Err("verification key is truncated".to_string())
+    );
+    assert_eq!(
+        verification_key(&[with_commitment.as_slice(), &[0, 0]].concat()),
+        Err("2 bytes follow the verification key".to_string())
+    );
+}
@@ -0,0 +1,12 @@
+#[cfg(test)]
+pub mod test;
+
+/// The version of the linked barretenberg, to include in bug reports.
+///
+/// barretenberg exports no version of its own, so it is recorded when this crate is built: the
+/// `BARRETENBERG_VERSION` environment variable if it was set, e.g. to the tag or commit
+/// `libbarretenberg.a` was built from, and otherwise `unknown+` followed by a fingerprint of the
+/// library, which at least tells apart builds linked against different libraries.
+pub fn barretenberg_version() -> String {
+    env!("BARRETENBERG_VERSION").to_owned()
+}
@@ -0,0 +1,9 @@
+use super::barretenberg_version;
+
+#[test]
+fn test_barretenberg_version() {
+    let version = barretenberg_version();
+    println!("barretenberg {version}");
+    assert!(!version.is_empty());
+    assert!(!version.contains(char::is_whitespace));
+}